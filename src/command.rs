@@ -1,13 +1,22 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use std::io::{self, IsTerminal as _, Read as _};
 use std::path::PathBuf;
 
-use clap::{Subcommand, ValueHint};
+use clap::{Subcommand, ValueEnum as _, ValueHint};
 use clap_complete::Shell;
+use colored::Colorize as _;
 use globset::Glob;
 
 use crate::output::Format;
 
+pub mod cache;
 pub mod check;
+pub mod config;
+pub mod fmt;
 pub mod generate_shell_completion;
+pub mod server;
 
 #[derive(Subcommand)]
 #[allow(clippy::exhaustive_enums)]
@@ -18,21 +27,145 @@ pub enum Command {
         #[arg(default_value = ".", value_hint = ValueHint::AnyPath)]
         files: Vec<PathBuf>,
 
-        /// Output format for violations. The default format is "concise"
+        /// Output format for violations. Can be repeated to print several formats in order. The default format is "concise"
         #[arg(value_enum, long = "output-format")]
-        output_format: Option<Format>,
+        output_format: Vec<Format>,
+
+        /// Write a report of another format to a file in addition to the output (e.g. "sarif=mado.sarif"). Can be repeated
+        #[arg(long = "output-file", value_name = "FORMAT=PATH", value_parser = parse_output_file)]
+        output_file: Vec<(Format, PathBuf)>,
 
         /// Only log errors
         #[arg(long, default_value_t = false)]
         quiet: bool,
 
+        /// Exit with a non-zero status code if there are more warnings than this
+        #[arg(long, value_name = "N")]
+        max_warnings: Option<usize>,
+
+        /// Apply fixes to resolve lint violations where possible
+        #[arg(long, default_value_t = false)]
+        fix: bool,
+
+        /// Report inline suppression comments that suppress no violations or refer to unknown rules
+        #[arg(long, default_value_t = false)]
+        report_unused_suppressions: bool,
+
+        /// Print a unified diff of suggested fixes without writing them. Exit with a non-zero status code if any fixes are suggested
+        #[arg(long, default_value_t = false, conflicts_with = "fix")]
+        diff: bool,
+
+        /// Lint all files without reading or writing the cache
+        #[arg(long, default_value_t = false)]
+        no_cache: bool,
+
+        /// Report only violations not recorded in the baseline file
+        #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+        baseline: Option<PathBuf>,
+
+        /// Record all violations to the baseline file instead of reporting them
+        #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath, conflicts_with_all = ["fix", "diff", "watch"])]
+        write_baseline: Option<PathBuf>,
+
         /// List of file patterns to exclude from linting
         #[arg(long, value_delimiter = ',')]
         exclude: Option<Vec<Glob>>,
+
+        /// Keep running and check changed files again. Changes to configuration files are applied without restart
+        #[arg(long, default_value_t = false, conflicts_with_all = ["fix", "diff"])]
+        watch: bool,
+
+        /// Only check files changed since the git revision, including uncommitted and untracked files, and report violations on changed lines
+        #[arg(long, value_name = "REV", conflicts_with_all = ["watch", "write_baseline"])]
+        changed_since: Option<String>,
+
+        /// Report violations anywhere in changed files instead of only on changed lines
+        #[arg(long, default_value_t = false, requires = "changed_since")]
+        whole_file: bool,
     },
+    /// Format markdown on the given files or directories
+    Fmt {
+        /// List of files or directories to format
+        #[arg(default_value = ".", value_hint = ValueHint::AnyPath)]
+        files: Vec<PathBuf>,
+
+        /// Avoid writing any formatted files back. Exit with a non-zero status code if any files would be reformatted
+        #[arg(long, default_value_t = false)]
+        check: bool,
+
+        /// List of file patterns to exclude from formatting
+        #[arg(long, value_delimiter = ',')]
+        exclude: Option<Vec<Glob>>,
+    },
+    /// Manage configuration files
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Manage the cache of lint results
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Run a language server over the standard input and output
+    Server,
     /// Generate shell completion
     GenerateShellCompletion {
         /// Shell to generate a completion script
         shell: Shell,
     },
 }
+
+#[derive(Subcommand)]
+#[allow(clippy::exhaustive_enums)]
+pub enum ConfigCommand {
+    /// Print mado.toml equivalent to a configuration file of markdownlint, markdownlint-cli2 or mdl
+    Migrate {
+        /// A path to the configuration file. Searched in the current directory if not given
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+#[allow(clippy::exhaustive_enums)]
+pub enum CacheCommand {
+    /// Remove the cache in the current directory
+    Clean,
+}
+
+fn parse_output_file(value: &str) -> Result<(Format, PathBuf), String> {
+    let (format, path) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected FORMAT=PATH, but got `{value}`"))?;
+    if path.is_empty() {
+        return Err(format!("missing a path for `{format}`"));
+    }
+
+    let format = Format::from_str(format, false)?;
+    Ok((format, PathBuf::from(path)))
+}
+
+// The same configuration can be loaded several times, but each warning is shown once
+fn print_warnings<'a>(warnings: impl IntoIterator<Item = &'a String>) {
+    let warnings: BTreeSet<_> = warnings.into_iter().collect();
+    for warning in warnings {
+        eprintln!("{} {warning}", "warning:".yellow().bold());
+    }
+}
+
+fn stdin_input() -> Option<String> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return None;
+    }
+
+    let mut buffer = String::new();
+    io::stdin().lock().read_to_string(&mut buffer).ok()?;
+
+    if buffer.is_empty() {
+        None
+    } else {
+        Some(buffer)
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use miette::Result;
+
+use crate::service::cache::Cache;
+
+pub struct CacheCleaner {
+    dir: PathBuf,
+}
+
+impl CacheCleaner {
+    #[inline]
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    #[inline]
+    pub fn clean(self) -> Result<ExitCode> {
+        if Cache::clean(&self.dir)? {
+            println!("Removed {}", self.dir.display());
+        } else {
+            println!("No cache found at {}", self.dir.display());
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
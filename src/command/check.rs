@@ -1,117 +1,447 @@
-use std::io::Read as _;
-use std::io::{self, BufWriter, IsTerminal as _, Write as _};
-use std::path::PathBuf;
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
+use colored::{Colorize as _, control};
 use globset::Glob;
-use miette::IntoDiagnostic as _;
 use miette::Result;
+use miette::{Context as _, IntoDiagnostic as _};
+
+use super::{print_warnings, stdin_input};
+use crate::config::Lint;
+use crate::fix::{self, Edit};
+use crate::output::{
+    self, Checkstyle, Concise, Diff, Format, Full, Github, Gitlab, Html, Json, JsonLines, Junit,
+    Markdownlint, Mdl, Sarif,
+};
+use crate::rule::Severity;
+use crate::service::baseline::Baseline;
+use crate::service::cache::Cache;
+use crate::service::changes::Changes;
+use crate::service::runner::{LintReport, LintRunner, ParallelLintRunner, StringLintRunner};
+use crate::{Config, Violation};
 
-use crate::Config;
-use crate::output::{Concise, Format, Markdownlint, Mdl};
-use crate::service::runner::{LintRunner, ParallelLintRunner, StringLintRunner};
+pub mod watch;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(clippy::exhaustive_structs)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Options {
     pub config_path: Option<PathBuf>,
-    pub output_format: Option<Format>,
+    pub output_format: Vec<Format>,
+    pub output_files: Vec<(Format, PathBuf)>,
     pub quiet: bool,
+    pub max_warnings: Option<usize>,
+    pub fix: bool,
+    pub report_unused_suppressions: bool,
+    pub no_cache: bool,
+    pub baseline: Option<PathBuf>,
     pub exclude: Option<Vec<Glob>>,
 }
 
 impl Options {
+    /// Returns the configuration overridden by the options and warnings from loading it
     #[inline]
-    pub fn to_config(self) -> Result<Config> {
-        let mut config = match self.config_path {
+    pub fn to_config(self) -> Result<(Config, Vec<String>)> {
+        let (mut config, warnings) = match self.config_path {
             Some(config_path) => Config::load(&config_path)?,
             None => Config::resolve()?,
         };
 
-        if let Some(format) = self.output_format {
-            config.lint.output_format = format;
+        if let Some(format) = self.output_format.first() {
+            config.lint.output_format = Some(format.clone());
         }
+        config.lint.reporters.extend(self.output_files);
 
         // Respect config
         config.lint.quiet |= self.quiet;
+        if let Some(max_warnings) = self.max_warnings {
+            config.lint.max_warnings = Some(max_warnings);
+        }
+        config.lint.fix |= self.fix;
+        config.lint.report_unused_suppressions |= self.report_unused_suppressions;
+        config.lint.cache &= !self.no_cache;
+        if let Some(baseline) = self.baseline {
+            config.lint.baseline = Some(baseline);
+        }
 
         if let Some(exclude) = self.exclude {
             config.lint.exclude = exclude;
         }
 
-        Ok(config)
+        Ok((config, warnings))
     }
 }
 
 pub struct Checker {
     runner: LintRunner,
     config: Config,
+    formats: Vec<Format>,
+    input: Option<String>,
+    diff: bool,
+    changes: Option<Changes>,
+    warnings: Vec<String>,
 }
 
-fn stdin_input() -> Option<String> {
-    let stdin = io::stdin();
-    if stdin.is_terminal() {
-        return None;
+impl Checker {
+    #[inline]
+    pub fn new(patterns: &[PathBuf], config: Config, discover: bool, diff: bool) -> Result<Self> {
+        let mut config = config;
+
+        // Never write files when only suggested fixes are shown
+        config.lint.fix &= !diff;
+
+        let input = stdin_input();
+        let runner = if let Some(input) = &input {
+            LintRunner::String(Box::new(StringLintRunner::new(
+                input.clone(),
+                config.clone(),
+            )))
+        } else {
+            let mut runner = ParallelLintRunner::new(patterns, config.clone(), 100)?;
+            if discover {
+                runner = runner.with_config_discovery();
+            }
+            // Fixed files are written back, so their violations are never stored
+            if config.lint.cache
+                && !config.lint.fix
+                && let Some(cache) = Self::open_cache()
+            {
+                runner = runner.with_cache(cache);
+            }
+            LintRunner::Parallel(Box::new(runner))
+        };
+
+        let format = config
+            .lint
+            .output_format
+            .clone()
+            .unwrap_or_else(Format::from_env);
+
+        Ok(Self {
+            runner,
+            config,
+            formats: vec![format],
+            input,
+            diff,
+            changes: None,
+            warnings: vec![],
+        })
     }
 
-    let mut buffer = String::new();
-    io::stdin().lock().read_to_string(&mut buffer).ok()?;
+    /// Prints violations in each of `formats` in order, unless it is empty
+    #[inline]
+    #[must_use]
+    pub fn with_output_formats(self, formats: Vec<Format>) -> Self {
+        if formats.is_empty() {
+            return self;
+        }
 
-    if buffer.is_empty() {
-        None
-    } else {
-        Some(buffer)
+        Self { formats, ..self }
     }
-}
 
-impl Checker {
+    /// Reports only violations on lines in `changes`
     #[inline]
-    pub fn new(patterns: &[PathBuf], config: Config) -> Result<Self> {
-        let runner = match stdin_input() {
-            Some(input) => {
-                LintRunner::String(Box::new(StringLintRunner::new(input, config.clone())))
-            }
-            None => LintRunner::Parallel(Box::new(ParallelLintRunner::new(
-                patterns,
-                config.clone(),
-                100,
-            )?)),
-        };
+    #[must_use]
+    pub fn with_changes(self, changes: Changes) -> Self {
+        Self {
+            changes: Some(changes),
+            ..self
+        }
+    }
 
-        Ok(Self { runner, config })
+    /// Prints `warnings` from loading the configuration together with those of discovered ones
+    #[inline]
+    #[must_use]
+    pub fn with_warnings(self, warnings: Vec<String>) -> Self {
+        Self { warnings, ..self }
     }
 
     #[inline]
     pub fn check(self) -> Result<ExitCode> {
-        let mut violations = self.runner.run()?;
-        violations.sort_by(self.config.lint.output_format.sorter());
+        let LintReport {
+            files,
+            mut violations,
+            warnings,
+            ..
+        } = self.runner.run()?;
+        print_warnings(self.warnings.iter().chain(&warnings));
+        if let Some(changes) = &self.changes {
+            violations.retain(|violation| changes.contains(violation));
+        }
+        let mut sources = BTreeMap::new();
+        if let Some(path) = &self.config.lint.baseline {
+            sources = Self::read_sources(&violations, self.input.as_deref())?;
+            violations = Baseline::load(path)?.filter(violations, &sources);
+        }
+        if self.diff {
+            return Self::print_diff(&violations, self.input.as_deref(), self.config.lint.quiet);
+        }
+
+        let quiet = self.config.lint.quiet;
+        Self::report_all(
+            &mut BufWriter::new(io::stdout().lock()),
+            &self.formats,
+            &self.config.lint,
+            &files,
+            &mut violations,
+            sources,
+            self.input.as_deref(),
+        )?;
+
+        let num_warnings = violations
+            .iter()
+            .filter(|violation| violation.severity() == Severity::Warning)
+            .count();
+        let has_errors = violations
+            .iter()
+            .any(|violation| violation.severity() == Severity::Error);
+        match self.config.lint.max_warnings {
+            Some(max_warnings) if num_warnings > max_warnings => {
+                if !quiet {
+                    eprintln!("Found more warnings than the maximum of {max_warnings}.");
+                }
+                Ok(ExitCode::FAILURE)
+            }
+            _ if has_errors => Ok(ExitCode::FAILURE),
+            _ => Ok(ExitCode::SUCCESS),
+        }
+    }
+
+    /// Records all violations to `path` instead of reporting them
+    #[inline]
+    pub fn write_baseline(self, path: &Path) -> Result<ExitCode> {
+        let LintReport {
+            violations,
+            warnings,
+            ..
+        } = self.runner.run()?;
+        print_warnings(self.warnings.iter().chain(&warnings));
+        let sources = Self::read_sources(&violations, self.input.as_deref())?;
+        Baseline::new(&violations, &sources).write(path)?;
+
+        if !self.config.lint.quiet {
+            println!(
+                "Wrote {} to {}.",
+                output::summarize(&violations),
+                path.display()
+            );
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    // The cache only speeds up linting, so files are still linted without it
+    fn open_cache() -> Option<Cache> {
+        match Cache::find_dir(Path::new(".")).and_then(|dir| Cache::open(&dir)) {
+            Ok(cache) => Some(cache),
+            Err(err) => {
+                eprintln!(
+                    "{} Failed to open the cache, so files are linted without it: {err}",
+                    "warning:".yellow().bold()
+                );
+                None
+            }
+        }
+    }
+
+    // Prints violations in each of `formats` to `output` and writes the reports of
+    // `reporters` to their files. `sources` are read unless given if any format needs them
+    fn report_all<W: Write>(
+        output: &mut W,
+        formats: &[Format],
+        lint: &Lint,
+        files: &[PathBuf],
+        violations: &mut [Violation],
+        sources: BTreeMap<PathBuf, String>,
+        input: Option<&str>,
+    ) -> Result<()> {
+        let mut sources = sources;
+        let mut all_formats = formats
+            .iter()
+            .chain(lint.reporters.iter().map(|(format, _)| format));
+        if sources.is_empty() && all_formats.any(Self::requires_sources) {
+            sources = Self::read_sources(violations, input)?;
+        }
+
+        for format in formats {
+            violations.sort_by(format.sorter());
+            Self::report(output, format, files, violations, &sources, lint.quiet)?;
+        }
+        output.flush().into_diagnostic()?;
 
+        if lint.reporters.is_empty() {
+            return Ok(());
+        }
+
+        // Reports written to files never contain colors
+        control::set_override(false);
+        let result = lint.reporters.iter().try_for_each(|(format, path)| {
+            violations.sort_by(format.sorter());
+            let file = File::create(path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+            let mut writer = BufWriter::new(file);
+            Self::report(&mut writer, format, files, violations, &sources, lint.quiet)?;
+            writer.flush().into_diagnostic()
+        });
+        control::unset_override();
+        result
+    }
+
+    // Writes `violations` sorted for `format` to `output`
+    fn report<W: Write>(
+        output: &mut W,
+        format: &Format,
+        files: &[PathBuf],
+        violations: &[Violation],
+        sources: &BTreeMap<PathBuf, String>,
+        quiet: bool,
+    ) -> Result<()> {
+        // Machine-readable formats print no messages around violations
+        match format {
+            Format::Json => {
+                writeln!(output, "{}", Json::new(files, violations)).into_diagnostic()?;
+            }
+            Format::JsonLines => {
+                for violation in violations {
+                    writeln!(output, "{}", JsonLines::new(violation)).into_diagnostic()?;
+                }
+            }
+            Format::Sarif => writeln!(output, "{}", Sarif::new(violations)).into_diagnostic()?,
+            Format::Junit => {
+                writeln!(output, "{}", Junit::new(files, violations)).into_diagnostic()?;
+            }
+            Format::Checkstyle => {
+                writeln!(output, "{}", Checkstyle::new(violations)).into_diagnostic()?;
+            }
+            Format::Gitlab => {
+                writeln!(output, "{}", Gitlab::new(violations, sources)).into_diagnostic()?;
+            }
+            Format::Html => {
+                writeln!(output, "{}", Html::new(files, violations, sources)).into_diagnostic()?;
+            }
+            Format::Concise
+            | Format::Mdl
+            | Format::Markdownlint
+            | Format::Github
+            | Format::Full => Self::report_violations(output, format, violations, sources, quiet)?,
+        }
+
+        Ok(())
+    }
+
+    fn report_violations<W: Write>(
+        output: &mut W,
+        format: &Format,
+        violations: &[Violation],
+        sources: &BTreeMap<PathBuf, String>,
+        quiet: bool,
+    ) -> Result<()> {
         if violations.is_empty() {
-            if !self.config.lint.quiet {
-                println!("All checks passed!");
+            if !quiet {
+                writeln!(output, "All checks passed!").into_diagnostic()?;
             }
 
-            return Ok(ExitCode::SUCCESS);
+            return Ok(());
         }
 
-        let mut output = BufWriter::new(io::stdout().lock());
-        let num_violations = violations.len();
         for violation in violations {
-            match self.config.lint.output_format {
+            match format {
                 Format::Concise => {
-                    writeln!(output, "{}", Concise::new(&violation)).into_diagnostic()?;
+                    writeln!(output, "{}", Concise::new(violation)).into_diagnostic()?;
                 }
-                Format::Mdl => writeln!(output, "{}", Mdl::new(&violation)).into_diagnostic()?,
+                Format::Mdl => writeln!(output, "{}", Mdl::new(violation)).into_diagnostic()?,
                 Format::Markdownlint => {
-                    writeln!(output, "{}", Markdownlint::new(&violation)).into_diagnostic()?;
+                    writeln!(output, "{}", Markdownlint::new(violation)).into_diagnostic()?;
                 }
+                Format::Github => {
+                    writeln!(output, "{}", Github::new(violation)).into_diagnostic()?;
+                }
+                Format::Full => {
+                    let source = sources.get(violation.path()).map_or("", String::as_str);
+                    writeln!(output, "{}", Full::new(violation, source)).into_diagnostic()?;
+                }
+                Format::Json
+                | Format::JsonLines
+                | Format::Sarif
+                | Format::Junit
+                | Format::Checkstyle
+                | Format::Gitlab
+                | Format::Html => {}
             }
         }
 
-        if num_violations == 1 {
-            writeln!(output, "\nFound 1 error.").into_diagnostic()?;
-        } else {
-            writeln!(output, "\nFound {num_violations} errors.").into_diagnostic()?;
+        writeln!(output, "\nFound {}.", output::summarize(violations)).into_diagnostic()?;
+
+        Ok(())
+    }
+
+    // Formats showing the source lines of violations
+    const fn requires_sources(format: &Format) -> bool {
+        matches!(format, Format::Gitlab | Format::Html | Format::Full)
+    }
+
+    // Reads the checked text, which is the standard input if given
+    fn read(path: &Path, input: Option<&str>) -> Result<String> {
+        input.map_or_else(
+            || fs::read_to_string(path).into_diagnostic(),
+            |input| Ok(input.to_owned()),
+        )
+    }
+
+    fn read_sources(
+        violations: &[Violation],
+        input: Option<&str>,
+    ) -> Result<BTreeMap<PathBuf, String>> {
+        let mut sources = BTreeMap::new();
+        for violation in violations {
+            if !sources.contains_key(violation.path()) {
+                let text = Self::read(violation.path(), input)?;
+                sources.insert(violation.path().clone(), text);
+            }
+        }
+
+        Ok(sources)
+    }
+
+    fn print_diff(violations: &[Violation], input: Option<&str>, quiet: bool) -> Result<ExitCode> {
+        let mut edits: BTreeMap<&Path, Vec<Edit>> = BTreeMap::new();
+        let mut num_fixable = 0;
+        for violation in violations {
+            if !violation.fixes().is_empty() {
+                num_fixable += 1;
+                edits
+                    .entry(violation.path())
+                    .or_default()
+                    .extend_from_slice(violation.fixes());
+            }
+        }
+
+        let mut output = BufWriter::new(io::stdout().lock());
+        for (path, path_edits) in edits {
+            let text = Self::read(path, input)?;
+
+            if let Some(fixed) = fix::apply(&text, path_edits) {
+                write!(output, "{}", Diff::new(path, &text, &fixed)).into_diagnostic()?;
+            }
+        }
+        output.flush().into_diagnostic()?;
+
+        if num_fixable == 0 {
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        if !quiet {
+            if num_fixable == 1 {
+                eprintln!("\nWould fix 1 error.");
+            } else {
+                eprintln!("\nWould fix {num_fixable} errors.");
+            }
         }
 
         Ok(ExitCode::FAILURE)
@@ -127,40 +457,59 @@ mod tests {
     use super::*;
 
     #[test]
-    fn options_to_config_none_none_false_none() -> Result<()> {
+    fn options_to_config_none_none_false_false_false_none() -> Result<()> {
         let options = Options {
             config_path: None,
-            output_format: None,
+            output_format: vec![],
+            output_files: vec![],
             quiet: false,
+            max_warnings: None,
+            fix: false,
+            report_unused_suppressions: false,
+            no_cache: false,
+            baseline: None,
             exclude: None,
         };
         let actual = options.to_config()?;
         let mut expected = Config::default();
+        expected.lint.output_format = Some(Format::Concise);
         expected.lint.md013.code_blocks = false;
         expected.lint.md013.tables = false;
         expected.lint.md024.allow_different_nesting = true;
-        assert_eq!(actual, expected);
+        assert_eq!(actual, (expected, vec![]));
         Ok(())
     }
 
     #[test]
-    fn options_to_config_some_some_true_some() -> Result<()> {
+    fn options_to_config_some_some_true_true_true_some() -> Result<()> {
         let exclude = vec![Glob::new("README.md").into_diagnostic()?];
         let options = Options {
             config_path: Some(Path::new("mado.toml").to_path_buf()),
-            output_format: Some(Format::Mdl),
+            output_format: vec![Format::Mdl, Format::Github],
+            output_files: vec![(Format::Sarif, Path::new("mado.sarif").to_path_buf())],
             quiet: true,
+            max_warnings: Some(0),
+            fix: true,
+            report_unused_suppressions: true,
+            no_cache: true,
+            baseline: Some(Path::new("mado-baseline.json").to_path_buf()),
             exclude: Some(exclude.clone()),
         };
         let actual = options.to_config()?;
         let mut expected = Config::default();
-        expected.lint.output_format = Format::Mdl;
+        expected.lint.output_format = Some(Format::Mdl);
+        expected.lint.reporters = vec![(Format::Sarif, Path::new("mado.sarif").to_path_buf())];
         expected.lint.quiet = true;
+        expected.lint.max_warnings = Some(0);
+        expected.lint.fix = true;
+        expected.lint.report_unused_suppressions = true;
+        expected.lint.cache = false;
+        expected.lint.baseline = Some(Path::new("mado-baseline.json").to_path_buf());
         expected.lint.exclude = exclude;
         expected.lint.md013.code_blocks = false;
         expected.lint.md013.tables = false;
         expected.lint.md024.allow_different_nesting = true;
-        assert_eq!(actual, expected);
+        assert_eq!(actual, (expected, vec![]));
         Ok(())
     }
 }
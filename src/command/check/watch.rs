@@ -0,0 +1,604 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::env;
+use std::io::{self, BufWriter, IsTerminal as _, Write};
+use std::path::{self, Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Mutex, mpsc};
+
+use ignore::WalkState;
+use miette::{IntoDiagnostic as _, Result, miette};
+use notify_debouncer_full::notify::{RecursiveMode, Watcher};
+use notify_debouncer_full::{Debouncer, FileIdCache, new_debouncer};
+
+use super::{Checker, Options};
+use crate::command::print_warnings;
+use crate::output::Format;
+use crate::service::baseline::Baseline;
+use crate::service::cache::Cache;
+use crate::service::runner::{LintReport, ParallelLintRunner};
+use crate::service::visitor::normalize_path;
+use crate::service::walker::WalkParallelBuilder;
+use crate::{Config, Violation};
+
+// Editors often write a file several times on save, so wait for them to settle
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+// Moves the cursor to the top left after clearing the screen
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Re-lints files whenever they or their configurations change
+pub struct FileWatcher {
+    patterns: Vec<PathBuf>,
+    options: Options,
+    config: Config,
+    // Warnings from loading the configuration and those found while linting
+    warnings: BTreeSet<String>,
+    discover: bool,
+    cache: Option<Cache>,
+    // Files extended by configurations, which are watched even outside the patterns
+    bases: BTreeSet<PathBuf>,
+    // Violations of each linted file, keyed by the walked path
+    violations: BTreeMap<PathBuf, Vec<Violation>>,
+}
+
+impl FileWatcher {
+    #[inline]
+    pub fn new(patterns: &[PathBuf], options: Options, discover: bool) -> Result<Self> {
+        let (config, warnings) = Self::load(&options)?;
+        let cache = if config.lint.cache {
+            Checker::open_cache()
+        } else {
+            None
+        };
+        let bases = Self::bases(&options)?.into_iter().collect();
+        Ok(Self {
+            patterns: patterns.to_vec(),
+            options,
+            config,
+            warnings: warnings.into_iter().collect(),
+            discover,
+            cache,
+            bases,
+            violations: BTreeMap::new(),
+        })
+    }
+
+    /// Lints all files, then keeps re-linting changed ones until interrupted
+    #[inline]
+    pub fn watch(mut self) -> Result<ExitCode> {
+        let (tx, rx) = mpsc::channel();
+        let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, tx).into_diagnostic()?;
+        for pattern in &self.patterns {
+            let path = path::absolute(pattern).into_diagnostic()?;
+            debouncer
+                .watch(&path, RecursiveMode::Recursive)
+                .into_diagnostic()?;
+        }
+        let mut watched = BTreeSet::new();
+        if let Some(config_path) = &self.options.config_path {
+            // The configuration may be outside the watched files
+            let path = path::absolute(config_path).into_diagnostic()?;
+            debouncer
+                .watch(&path, RecursiveMode::NonRecursive)
+                .into_diagnostic()?;
+            watched.insert(path);
+        }
+
+        let clear = io::stdout().is_terminal();
+        self.update(&[])?;
+        self.watch_bases(&mut debouncer, &mut watched)?;
+        self.render(&mut BufWriter::new(io::stdout().lock()), clear)?;
+        for result in rx {
+            let changed: Vec<PathBuf> = match result {
+                // Reading files to lint them must not be taken as a change
+                Ok(events) => events
+                    .into_iter()
+                    .filter(|event| !event.kind.is_access())
+                    .flat_map(|event| event.event.paths)
+                    .collect(),
+                Err(errors) => {
+                    for err in errors {
+                        eprintln!("{err}");
+                    }
+                    continue;
+                }
+            };
+            // Keep watching even if the files cannot be linted while being edited
+            match self.update(&changed) {
+                Ok(true) => self.render(&mut BufWriter::new(io::stdout().lock()), clear)?,
+                Ok(false) => {}
+                Err(err) => eprintln!("{err:?}"),
+            }
+            self.watch_bases(&mut debouncer, &mut watched)?;
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    // Watches extended configuration files that are not watched yet
+    fn watch_bases<T: Watcher, C: FileIdCache>(
+        &self,
+        debouncer: &mut Debouncer<T, C>,
+        watched: &mut BTreeSet<PathBuf>,
+    ) -> Result<()> {
+        for base in &self.bases {
+            if watched.insert(base.clone()) {
+                debouncer
+                    .watch(base, RecursiveMode::NonRecursive)
+                    .into_diagnostic()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load(options: &Options) -> Result<(Config, Vec<String>)> {
+        let (mut config, warnings) = options.clone().to_config()?;
+        // Writing fixes would be detected as another change
+        config.lint.fix = false;
+        Ok((config, warnings))
+    }
+
+    // Returns the files extended by the configuration loaded by `options`
+    fn bases(options: &Options) -> Result<Vec<PathBuf>> {
+        let config_path = match &options.config_path {
+            Some(config_path) => Some(config_path.clone()),
+            None => Config::find_nearest(&env::current_dir().into_diagnostic()?)?,
+        };
+        config_path.map_or_else(|| Ok(vec![]), |path| Config::bases(&path))
+    }
+
+    fn is_config(&self, path: &Path) -> Result<bool> {
+        match &self.options.config_path {
+            Some(config_path) => Ok(path::absolute(config_path).into_diagnostic()? == path),
+            None => Ok(Config::is_config_file(path)),
+        }
+    }
+
+    // Re-lints the files affected by `changed` paths and returns true if any results may change
+    fn update(&mut self, changed: &[PathBuf]) -> Result<bool> {
+        let changed = changed
+            .iter()
+            .map(path::absolute)
+            .collect::<Result<BTreeSet<_>, _>>()
+            .into_diagnostic()?;
+        let mut config_dirs = vec![];
+        for path in &changed {
+            if self.is_config(path)?
+                && let Some(dir) = path.parent()
+            {
+                config_dirs.push(dir.to_path_buf());
+            }
+        }
+        // Extended files may be shared by any configuration, so all files are affected
+        let is_base_changed = changed.iter().any(|path| self.bases.contains(path));
+        let is_config_changed = is_base_changed || !config_dirs.is_empty();
+
+        if is_config_changed {
+            let (config, warnings) = Self::load(&self.options)?;
+            self.config = config;
+            self.warnings = warnings.into_iter().collect();
+            self.bases.extend(Self::bases(&self.options)?);
+            if self.config.lint.cache && self.cache.is_none() {
+                self.cache = Checker::open_cache();
+            }
+        }
+        // An explicit configuration applies to all files wherever it is
+        let relint_all = is_base_changed || (!config_dirs.is_empty() && !self.discover);
+
+        // Walking is only needed when the set of files to lint may change
+        let known_files = self
+            .violations
+            .keys()
+            .map(|file| path::absolute(file).map(|absolute_path| (absolute_path, file.clone())))
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .into_diagnostic()?;
+        let requires_walk = changed.is_empty()
+            || is_config_changed
+            || changed
+                .iter()
+                .any(|path| !known_files.contains_key(path) && Self::may_add_files(path));
+        if !requires_walk {
+            return self.update_known(&known_files, &changed);
+        }
+
+        let walked_files = self.files()?;
+        let mut targets = vec![];
+        for file in &walked_files {
+            let absolute_path = path::absolute(file).into_diagnostic()?;
+            if relint_all
+                || !self.violations.contains_key(file)
+                || changed.contains(&absolute_path)
+                || config_dirs.iter().any(|dir| absolute_path.starts_with(dir))
+            {
+                targets.push(file.clone());
+            }
+        }
+
+        // Forget deleted or newly excluded files
+        let num_files = self.violations.len();
+        let walked: BTreeSet<&PathBuf> = walked_files.iter().collect();
+        self.violations.retain(|path, _| walked.contains(path));
+        let has_removed = self.violations.len() != num_files;
+
+        if targets.is_empty() {
+            return Ok(has_removed);
+        }
+
+        self.insert(self.lint(&targets)?);
+        Ok(true)
+    }
+
+    // Re-lints known files under `changed` paths without walking, and forgets deleted ones
+    fn update_known(
+        &mut self,
+        known_files: &BTreeMap<PathBuf, PathBuf>,
+        changed: &BTreeSet<PathBuf>,
+    ) -> Result<bool> {
+        let mut targets = vec![];
+        let mut has_removed = false;
+        for (absolute_path, file) in known_files {
+            if !changed.iter().any(|path| absolute_path.starts_with(path)) {
+                continue;
+            }
+
+            if absolute_path.is_file() {
+                targets.push(file.clone());
+            } else {
+                self.violations.remove(file);
+                has_removed = true;
+            }
+        }
+
+        if targets.is_empty() {
+            return Ok(has_removed);
+        }
+
+        self.insert(self.lint(&targets)?);
+        Ok(true)
+    }
+
+    // Returns true if `path` may add files to lint, such as new Markdown files,
+    // directories or ignore files
+    fn may_add_files(path: &Path) -> bool {
+        path.is_dir()
+            || path.extension() == Some("md".as_ref())
+            || path
+                .file_name()
+                .is_some_and(|file_name| file_name == ".gitignore" || file_name == ".ignore")
+    }
+
+    fn insert(&mut self, report: LintReport) {
+        let LintReport {
+            files,
+            violations,
+            warnings,
+            config_bases,
+        } = report;
+        self.warnings.extend(warnings);
+        self.bases.extend(config_bases);
+        for file in files {
+            self.violations.insert(file, vec![]);
+        }
+        for violation in violations {
+            self.violations
+                .entry(violation.path().clone())
+                .or_default()
+                .push(violation);
+        }
+    }
+
+    fn lint(&self, files: &[PathBuf]) -> Result<LintReport> {
+        // A new runner resolves configurations again, so that changes to them are applied
+        let mut runner = ParallelLintRunner::new(files, self.config.clone(), 100)?;
+        if self.config.lint.cache
+            && let Some(cache) = &self.cache
+        {
+            runner = runner.with_cache(cache.clone());
+        }
+        if self.discover {
+            runner.with_config_discovery().run()
+        } else {
+            runner.run()
+        }
+    }
+
+    // Returns the Markdown files to lint, respecting ignore files and exclusions
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let walker = WalkParallelBuilder::build(
+            &self.patterns,
+            self.config.lint.respect_ignore,
+            self.config.lint.respect_gitignore,
+        )?;
+        let exclusion = self.config.lint.exclude_set()?;
+        let paths = Arc::new(Mutex::new(vec![]));
+        walker.run(|| {
+            let local_paths = Arc::clone(&paths);
+            let exclusion = exclusion.clone();
+            Box::new(move |either_entry| {
+                if let Ok(entry) = either_entry
+                    && entry.path().is_file()
+                    && entry.path().extension() == Some("md".as_ref())
+                    && !exclusion.is_match(normalize_path(entry.path()))
+                    && let Ok(mut acquired_paths) = local_paths.lock()
+                {
+                    acquired_paths.push(entry.into_path());
+                }
+
+                WalkState::Continue
+            })
+        });
+
+        let lock = Arc::into_inner(paths).ok_or_else(|| miette!("Failed to unwrap Arc"))?;
+        let mut files = lock.into_inner().into_diagnostic()?;
+        files.sort();
+        Ok(files)
+    }
+
+    fn render<W: Write>(&self, output: &mut W, clear: bool) -> Result<()> {
+        // Formats given on the command line take precedence, as in one-shot checks
+        let formats = if self.options.output_format.is_empty() {
+            vec![
+                self.config
+                    .lint
+                    .output_format
+                    .clone()
+                    .unwrap_or_else(Format::from_env),
+            ]
+        } else {
+            self.options.output_format.clone()
+        };
+        let files: Vec<PathBuf> = self.violations.keys().cloned().collect();
+        let mut violations: Vec<Violation> = self.violations.values().flatten().cloned().collect();
+        let mut sources = BTreeMap::new();
+        if let Some(path) = &self.config.lint.baseline {
+            sources = Checker::read_sources(&violations, None)?;
+            violations = Baseline::load(path)?.filter(violations, &sources);
+        }
+
+        if clear {
+            write!(output, "{CLEAR_SCREEN}").into_diagnostic()?;
+            // Warnings are printed to the standard error, so they must not be cleared
+            output.flush().into_diagnostic()?;
+        }
+        print_warnings(&self.warnings);
+        Checker::report_all(
+            output,
+            &formats,
+            &self.config.lint,
+            &files,
+            &mut violations,
+            sources,
+            None,
+        )?;
+        if !self.config.lint.quiet {
+            if files.len() == 1 {
+                writeln!(output, "\nWatching 1 file for changes...").into_diagnostic()?;
+            } else {
+                writeln!(output, "\nWatching {} files for changes...", files.len())
+                    .into_diagnostic()?;
+            }
+        }
+        output.flush().into_diagnostic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+    use std::fs;
+
+    use colored::control;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    // Creates an empty configuration not to be affected by the one of this repository
+    fn empty_config(dir: &Path) -> Result<Option<PathBuf>> {
+        let path = dir.join("mado.toml");
+        fs::write(&path, "").into_diagnostic()?;
+        Ok(Some(path))
+    }
+
+    fn options(config_path: Option<PathBuf>) -> Options {
+        Options {
+            config_path,
+            output_format: vec![Format::Concise],
+            output_files: vec![],
+            quiet: false,
+            max_warnings: None,
+            fix: false,
+            report_unused_suppressions: false,
+            no_cache: true,
+            baseline: None,
+            exclude: None,
+        }
+    }
+
+    fn names<'a>(watcher: &'a FileWatcher, path: &Path) -> Vec<&'a str> {
+        watcher
+            .violations
+            .get(path)
+            .map(|violations| violations.iter().map(Violation::name).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn update() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let config_path = tmp_dir.path().join("mado.toml");
+        fs::write(&config_path, "").into_diagnostic()?;
+        let path = tmp_dir.path().join("a.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+        let other_path = tmp_dir.path().join("b.md");
+        fs::write(&other_path, "# World\n").into_diagnostic()?;
+
+        let patterns = [tmp_dir.path().to_path_buf()];
+        let mut watcher = FileWatcher::new(&patterns, options(Some(config_path.clone())), false)?;
+        assert!(watcher.update(&[])?);
+        assert_eq!(names(&watcher, &path), vec!["MD026"]);
+        assert_eq!(names(&watcher, &other_path), Vec::<&str>::new());
+        assert_eq!(watcher.violations.len(), 2);
+
+        // Nothing to do for unrelated files
+        fs::write(tmp_dir.path().join("c.txt"), "").into_diagnostic()?;
+        assert!(!watcher.update(&[tmp_dir.path().join("c.txt")])?);
+
+        // Only changed files are linted again
+        fs::write(&path, "# Hello\n").into_diagnostic()?;
+        fs::write(&other_path, "# World!\n").into_diagnostic()?;
+        assert!(watcher.update(slice::from_ref(&path))?);
+        assert_eq!(names(&watcher, &path), Vec::<&str>::new());
+        assert_eq!(names(&watcher, &other_path), Vec::<&str>::new());
+
+        // Files are walked again only when new ones may be added
+        let new_path = tmp_dir.path().join("d.md");
+        fs::write(&new_path, "# New.\n").into_diagnostic()?;
+        assert!(watcher.update(slice::from_ref(&path))?);
+        assert!(!watcher.violations.contains_key(&new_path));
+        assert!(watcher.update(slice::from_ref(&new_path))?);
+        assert_eq!(names(&watcher, &new_path), vec!["MD026"]);
+        fs::remove_file(&new_path).into_diagnostic()?;
+        assert!(watcher.update(slice::from_ref(&new_path))?);
+        assert!(!watcher.violations.contains_key(&new_path));
+
+        // All files are linted again with the reloaded configuration
+        let config = indoc! {r#"
+            [lint]
+            rules = ["MD026", "MD041"]
+        "#};
+        fs::write(&config_path, config).into_diagnostic()?;
+        assert!(watcher.update(&[config_path])?);
+        assert_eq!(names(&watcher, &other_path), vec!["MD026"]);
+
+        // Deleted files are forgotten
+        fs::remove_file(&other_path).into_diagnostic()?;
+        assert!(watcher.update(slice::from_ref(&other_path))?);
+        assert!(!watcher.violations.contains_key(&other_path));
+        Ok(())
+    }
+
+    #[test]
+    fn update_with_discovered_config() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::create_dir(tmp_dir.path().join(".git")).into_diagnostic()?;
+        let docs_dir = tmp_dir.path().join("docs");
+        fs::create_dir(&docs_dir).into_diagnostic()?;
+        let path = tmp_dir.path().join("README.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+        let docs_path = docs_dir.join("index.md");
+        fs::write(&docs_path, "# Hello.\n").into_diagnostic()?;
+
+        let patterns = [tmp_dir.path().to_path_buf()];
+        let mut watcher = FileWatcher::new(&patterns, options(None), true)?;
+        watcher.update(&[])?;
+        assert_eq!(names(&watcher, &path), vec!["MD026"]);
+        assert_eq!(names(&watcher, &docs_path), vec!["MD026"]);
+
+        // Only files under the changed configuration are linted again
+        let config_path = docs_dir.join("mado.toml");
+        fs::write(&config_path, "[lint]\nrules = []\n").into_diagnostic()?;
+        fs::write(&path, "# Hello\n").into_diagnostic()?;
+        assert!(watcher.update(&[config_path])?);
+        assert_eq!(names(&watcher, &path), vec!["MD026"]);
+        assert_eq!(names(&watcher, &docs_path), Vec::<&str>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn update_with_extended_config() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let docs_dir = tmp_dir.path().join("docs");
+        fs::create_dir(&docs_dir).into_diagnostic()?;
+        let config_path = docs_dir.join("mado.toml");
+        fs::write(&config_path, "extends = ['../base.toml']").into_diagnostic()?;
+        let base_path = tmp_dir.path().join("base.toml");
+        fs::write(&base_path, "").into_diagnostic()?;
+        let path = docs_dir.join("a.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+
+        let patterns = [docs_dir];
+        let mut watcher = FileWatcher::new(&patterns, options(Some(config_path)), false)?;
+        watcher.update(&[])?;
+        assert_eq!(names(&watcher, &path), vec!["MD026"]);
+
+        // The extended file is outside the watched files but still applied on change
+        let base_path = fs::canonicalize(&base_path).into_diagnostic()?;
+        assert!(watcher.bases.contains(&base_path));
+        fs::write(&base_path, "[lint]\nrules = []\n").into_diagnostic()?;
+        assert!(watcher.update(&[base_path])?);
+        assert_eq!(names(&watcher, &path), Vec::<&str>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn update_with_exclusion() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::write(tmp_dir.path().join(".ignore"), "ignored.md\n").into_diagnostic()?;
+        let path = tmp_dir.path().join("ignored.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+
+        let patterns = [tmp_dir.path().to_path_buf()];
+        let config_path = empty_config(tmp_dir.path())?;
+        let mut watcher = FileWatcher::new(&patterns, options(config_path), false)?;
+        assert!(!watcher.update(&[path])?);
+        assert!(watcher.violations.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial(colored)]
+    fn render() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("a.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+
+        let patterns = [tmp_dir.path().to_path_buf()];
+        let config_path = empty_config(tmp_dir.path())?;
+        let mut watcher = FileWatcher::new(&patterns, options(config_path), false)?;
+        watcher.update(&[])?;
+        control::set_override(false);
+        let mut output = vec![];
+        watcher.render(&mut output, true)?;
+        control::unset_override();
+        let actual = String::from_utf8(output).into_diagnostic()?;
+        let expected = format!(
+            "{CLEAR_SCREEN}{}:1:1: MD026 Trailing punctuation in header\n\nFound 1 error.\n\nWatching 1 file for changes...\n",
+            path.display()
+        );
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    #[serial(colored)]
+    fn render_with_reporter() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("a.md");
+        fs::write(&path, "# Hello.\n").into_diagnostic()?;
+
+        let patterns = [tmp_dir.path().to_path_buf()];
+        let report_path = tmp_dir.path().join("report.txt");
+        let options = Options {
+            output_files: vec![(Format::Concise, report_path.clone())],
+            ..options(empty_config(tmp_dir.path())?)
+        };
+        let mut watcher = FileWatcher::new(&patterns, options, false)?;
+        watcher.update(&[])?;
+        watcher.render(&mut vec![], false)?;
+        let actual = fs::read_to_string(&report_path).into_diagnostic()?;
+        let expected = format!(
+            "{}:1:1: MD026 Trailing punctuation in header\n\nFound 1 error.\n",
+            path.display()
+        );
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use std::env;
+use std::io::{self, BufWriter, Write as _};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use colored::Colorize as _;
+use miette::{IntoDiagnostic as _, Result, miette};
+
+use crate::config::migration::Migration;
+
+pub struct ConfigMigrator {
+    path: Option<PathBuf>,
+}
+
+impl ConfigMigrator {
+    #[inline]
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+
+    #[inline]
+    pub fn migrate(self) -> Result<ExitCode> {
+        let path = if let Some(path) = self.path {
+            path
+        } else {
+            let current_dir = env::current_dir().into_diagnostic()?;
+            Migration::find(&current_dir)?.ok_or_else(|| {
+                miette!(
+                    "No configuration file found. Expected one of {}",
+                    Migration::FILE_NAMES.join(", ")
+                )
+            })?
+        };
+
+        let migration = Migration::load(&path)?;
+        for warning in migration.warnings() {
+            eprintln!("{} {warning}", "warning:".yellow().bold());
+        }
+
+        // Check that the output is a valid configuration
+        migration.to_config()?;
+        let toml = toml::to_string_pretty(&migration.to_table()).into_diagnostic()?;
+        let mut output = BufWriter::new(io::stdout().lock());
+        write!(output, "{toml}").into_diagnostic()?;
+        output.flush().into_diagnostic()?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -0,0 +1,241 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::result;
+use std::fs;
+use std::io::{self, BufWriter, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+
+use globset::{Glob, GlobSet};
+use ignore::{DirEntry, WalkState};
+use miette::{IntoDiagnostic as _, Result, miette};
+
+use super::{print_warnings, stdin_input};
+use crate::Config;
+use crate::service::Formatter;
+use crate::service::resolver::ConfigResolver;
+use crate::service::visitor::is_markdown_file;
+use crate::service::walker::WalkParallelBuilder;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct Options {
+    pub config_path: Option<PathBuf>,
+    pub exclude: Option<Vec<Glob>>,
+}
+
+impl Options {
+    /// Returns the configuration overridden by the options and warnings from loading it
+    #[inline]
+    pub fn to_config(self) -> Result<(Config, Vec<String>)> {
+        let (mut config, warnings) = match self.config_path {
+            Some(config_path) => Config::load(&config_path)?,
+            None => Config::resolve()?,
+        };
+
+        if let Some(exclude) = self.exclude {
+            config.lint.exclude = exclude;
+        }
+
+        Ok((config, warnings))
+    }
+}
+
+pub struct FileFormatter {
+    patterns: Vec<PathBuf>,
+    config: Config,
+    check: bool,
+    discover: bool,
+    warnings: Vec<String>,
+}
+
+impl FileFormatter {
+    #[inline]
+    #[must_use]
+    pub fn new(patterns: &[PathBuf], config: Config, discover: bool, check: bool) -> Self {
+        Self {
+            patterns: patterns.to_vec(),
+            config,
+            check,
+            discover,
+            warnings: vec![],
+        }
+    }
+
+    /// Prints `warnings` from loading the configuration together with those of discovered ones
+    #[inline]
+    #[must_use]
+    pub fn with_warnings(self, warnings: Vec<String>) -> Self {
+        Self { warnings, ..self }
+    }
+
+    #[inline]
+    pub fn format(self) -> Result<ExitCode> {
+        if let Some(input) = stdin_input() {
+            print_warnings(&self.warnings);
+            return self.format_stdin(&Formatter::from(&self.config.lint), &input);
+        }
+
+        let resolver = if self.discover {
+            ConfigResolver::discover(self.config.clone())?
+        } else {
+            ConfigResolver::new(self.config.clone())?
+        };
+        let mut results = self.format_files(&resolver)?;
+        results.sort();
+        print_warnings(self.warnings.iter().chain(&resolver.warnings()?));
+
+        let mut output = BufWriter::new(io::stdout().lock());
+        let num_changed = results.iter().filter(|(_, changed)| *changed).count();
+        let num_unchanged = results.len() - num_changed;
+        if self.check {
+            for (path, _) in results.iter().filter(|(_, changed)| *changed) {
+                writeln!(output, "Would reformat: {}", path.display()).into_diagnostic()?;
+            }
+
+            writeln!(
+                output,
+                "{} would be reformatted, {} already formatted",
+                Self::files(num_changed),
+                Self::files(num_unchanged)
+            )
+            .into_diagnostic()?;
+        } else {
+            writeln!(
+                output,
+                "{} reformatted, {} left unchanged",
+                Self::files(num_changed),
+                Self::files(num_unchanged)
+            )
+            .into_diagnostic()?;
+        }
+
+        if self.check && num_changed > 0 {
+            Ok(ExitCode::FAILURE)
+        } else {
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+
+    fn format_stdin(&self, formatter: &Formatter, input: &str) -> Result<ExitCode> {
+        let output = formatter.format(Path::new("(stdin)"), input.to_owned())?;
+        if self.check {
+            if output == input {
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            println!("Would reformat: (stdin)");
+            return Ok(ExitCode::FAILURE);
+        }
+
+        print!("{output}");
+        Ok(ExitCode::SUCCESS)
+    }
+
+    // TODO: Don't use expect
+    #[expect(clippy::expect_used)]
+    fn format_files(&self, resolver: &ConfigResolver) -> Result<Vec<(PathBuf, bool)>> {
+        let walker = WalkParallelBuilder::build(
+            &self.patterns,
+            self.config.lint.respect_ignore,
+            self.config.lint.respect_gitignore,
+        )?;
+        let exclusion = self.config.lint.exclude_set()?;
+        let results: Arc<Mutex<Vec<(PathBuf, bool)>>> = Arc::new(Mutex::new(vec![]));
+
+        walker.run(|| {
+            let exclusion = exclusion.clone();
+            let results = Arc::clone(&results);
+            let check = self.check;
+            Box::new(move |either_entry| {
+                match Self::format_file(resolver, &exclusion, check, either_entry) {
+                    Ok(Some(result)) => results.lock().expect("lock must be acquired").push(result),
+                    Ok(None) => {}
+                    // TODO: Handle errors
+                    Err(err) => eprintln!("{err}"),
+                }
+
+                WalkState::Continue
+            })
+        });
+
+        let mutex = Arc::into_inner(results).ok_or_else(|| miette!("Failed to unwrap Arc"))?;
+        mutex.into_inner().into_diagnostic()
+    }
+
+    fn format_file(
+        resolver: &ConfigResolver,
+        exclusion: &GlobSet,
+        check: bool,
+        either_entry: result::Result<DirEntry, ignore::Error>,
+    ) -> Result<Option<(PathBuf, bool)>> {
+        let entry = either_entry.into_diagnostic()?;
+        let path = entry.path();
+        if !is_markdown_file(path, exclusion) {
+            return Ok(None);
+        }
+
+        // Apply the nearest configuration and its overrides in the same way as linting
+        let nearest = resolver.resolve(path)?;
+        if nearest.is_excluded(path)? {
+            return Ok(None);
+        }
+        let lint = nearest
+            .config
+            .lint
+            .overridden(&nearest.matched_overrides(path)?)?;
+        let formatter = Formatter::from(&lint);
+
+        let text = fs::read_to_string(path).into_diagnostic()?;
+        let output = formatter.format(path, text.clone())?;
+        let changed = output != text;
+        if changed && !check {
+            fs::write(path, output).into_diagnostic()?;
+        }
+
+        Ok(Some((path.to_path_buf(), changed)))
+    }
+
+    fn files(count: usize) -> String {
+        if count == 1 {
+            "1 file".to_owned()
+        } else {
+            format!("{count} files")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn options_to_config_none_none() -> Result<()> {
+        let options = Options {
+            config_path: None,
+            exclude: None,
+        };
+        let actual = options.to_config()?;
+        let expected = Config::resolve()?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn options_to_config_some_some() -> Result<()> {
+        let exclude = vec![Glob::new("README.md").into_diagnostic()?];
+        let options = Options {
+            config_path: Some(Path::new("mado.toml").to_path_buf()),
+            exclude: Some(exclude.clone()),
+        };
+        let actual = options.to_config()?;
+        let (mut expected, _) = Config::load("mado.toml")?;
+        expected.lint.exclude = exclude;
+        assert_eq!(actual, (expected, vec![]));
+        Ok(())
+    }
+}
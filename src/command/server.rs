@@ -0,0 +1,768 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::iter;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use comrak::Arena;
+use comrak::nodes::Sourcepos;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, RegisterCapability, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CodeDescription, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, FileSystemWatcher, GlobPattern, InitializeParams, NumberOrString,
+    Position, PublishDiagnosticsParams, Range, Registration, RegistrationParams,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+    WorkspaceEdit,
+};
+use miette::{IntoDiagnostic as _, Result};
+use serde::de::DeserializeOwned;
+
+use crate::output::RULES_URI;
+use crate::rule::Severity;
+use crate::service::Linter;
+use crate::service::resolver::ConfigResolver;
+use crate::{Config, Document, Violation};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct Options {
+    pub config_path: Option<PathBuf>,
+}
+
+impl Options {
+    #[inline]
+    pub fn to_config(self) -> Result<Config> {
+        // Warnings are not printed since the standard error may be shown by clients
+        let (config, _) = match self.config_path {
+            Some(config_path) => Config::load(&config_path)?,
+            None => Config::resolve()?,
+        };
+
+        Ok(config)
+    }
+}
+
+// A document opened in the editor together with its latest violations
+struct OpenDocument {
+    text: String,
+    violations: Vec<Violation>,
+}
+
+/// A language server publishing violations of open documents as diagnostics
+pub struct LanguageServer {
+    resolver: ConfigResolver,
+    documents: BTreeMap<Url, OpenDocument>,
+}
+
+impl LanguageServer {
+    /// Discovers the nearest configuration for each document if `discover` is true
+    #[inline]
+    pub fn new(config: Config, discover: bool) -> Result<Self> {
+        let resolver = if discover {
+            ConfigResolver::discover(config)?
+        } else {
+            ConfigResolver::new(config)?
+        };
+
+        Ok(Self {
+            resolver,
+            documents: BTreeMap::new(),
+        })
+    }
+
+    /// Serves over the standard input and output until the client exits
+    #[inline]
+    pub fn serve(mut self) -> Result<ExitCode> {
+        let (connection, io_threads) = Connection::stdio();
+        self.run(&connection)?;
+        drop(connection);
+        io_threads.join().into_diagnostic()?;
+        Ok(ExitCode::SUCCESS)
+    }
+
+    /// Handles messages from `connection` until a shutdown is requested
+    #[inline]
+    pub fn run(&mut self, connection: &Connection) -> Result<()> {
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                ..CodeActionOptions::default()
+            })),
+            ..ServerCapabilities::default()
+        };
+        let capabilities = serde_json::to_value(capabilities).into_diagnostic()?;
+        let initialize_params = connection.initialize(capabilities).into_diagnostic()?;
+        let client: InitializeParams =
+            serde_json::from_value(initialize_params).into_diagnostic()?;
+        let can_watch_files = client
+            .capabilities
+            .workspace
+            .and_then(|workspace| workspace.did_change_watched_files)
+            .and_then(|watched_files| watched_files.dynamic_registration)
+            .unwrap_or(false);
+        if can_watch_files {
+            let request = Self::watch_config_files()?;
+            connection.sender.send(request.into()).into_diagnostic()?;
+        }
+
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request).into_diagnostic()? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(response.into()).into_diagnostic()?;
+                }
+                Message::Notification(notification) => {
+                    // Keep serving other documents even if one of them cannot be linted
+                    match self.handle_notification(notification) {
+                        Ok(publishes) => {
+                            for params in publishes {
+                                let method = PublishDiagnostics::METHOD.to_owned();
+                                let publish = Notification::new(method, params);
+                                connection.sender.send(publish.into()).into_diagnostic()?;
+                            }
+                        }
+                        Err(err) => eprintln!("{err:?}"),
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    // Asks the client to notify changes of configuration files, which are cached otherwise
+    fn watch_config_files() -> Result<Request> {
+        let watchers = Config::file_names()
+            .map(|file_name| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(format!("**/{file_name}")),
+                kind: None,
+            })
+            .collect();
+        let options = DidChangeWatchedFilesRegistrationOptions { watchers };
+        let registration = Registration {
+            id: "mado/watchConfigFiles".to_owned(),
+            method: DidChangeWatchedFiles::METHOD.to_owned(),
+            register_options: Some(serde_json::to_value(options).into_diagnostic()?),
+        };
+        let params = RegistrationParams {
+            registrations: vec![registration],
+        };
+        let id = RequestId::from("mado/watchConfigFiles".to_owned());
+        Ok(Request::new(
+            id,
+            RegisterCapability::METHOD.to_owned(),
+            params,
+        ))
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let Request { id, method, params } = request;
+        match method.as_str() {
+            CodeActionRequest::METHOD => match serde_json::from_value(params) {
+                Ok(params) => Response::new_ok(id, self.code_actions(&params)),
+                Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+            },
+            _ => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported method: {method}"),
+            ),
+        }
+    }
+
+    // Returns diagnostics to publish, if any document is opened, changed or closed,
+    // or all open documents are linted again after configuration files change
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Vec<PublishDiagnosticsParams>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = Self::params(notification)?;
+                let document = params.text_document;
+                let diagnostics = self.update(&document.uri, document.text)?;
+                Ok(vec![PublishDiagnosticsParams::new(
+                    document.uri,
+                    diagnostics,
+                    Some(document.version),
+                )])
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = Self::params(notification)?;
+                // The whole text is sent as the server only supports full synchronization
+                let Some(change) = params.content_changes.into_iter().last() else {
+                    return Ok(vec![]);
+                };
+                let document = params.text_document;
+                let diagnostics = self.update(&document.uri, change.text)?;
+                Ok(vec![PublishDiagnosticsParams::new(
+                    document.uri,
+                    diagnostics,
+                    Some(document.version),
+                )])
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = Self::params(notification)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                Ok(vec![PublishDiagnosticsParams::new(uri, vec![], None)])
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams = Self::params(notification)?;
+                let is_config_changed = params.changes.iter().any(|change| {
+                    change
+                        .uri
+                        .to_file_path()
+                        .is_ok_and(|path| Config::is_config_file(&path))
+                });
+                if !is_config_changed {
+                    return Ok(vec![]);
+                }
+
+                self.resolver.clear()?;
+                let documents: Vec<_> = self
+                    .documents
+                    .iter()
+                    .map(|(uri, document)| (uri.clone(), document.text.clone()))
+                    .collect();
+                let mut publishes = vec![];
+                for (uri, text) in documents {
+                    let diagnostics = self.update(&uri, text)?;
+                    publishes.push(PublishDiagnosticsParams::new(uri, diagnostics, None));
+                }
+                Ok(publishes)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn params<P: DeserializeOwned>(notification: Notification) -> Result<P> {
+        serde_json::from_value(notification.params).into_diagnostic()
+    }
+
+    // Lints `text` and keeps it as the content of `uri`
+    fn update(&mut self, uri: &Url, text: String) -> Result<Vec<Diagnostic>> {
+        let violations = self.lint(uri, &text)?;
+        let index = LineIndex::new(&text);
+        let diagnostics = violations
+            .iter()
+            .map(|violation| Self::diagnostic(&index, violation))
+            .collect();
+        self.documents
+            .insert(uri.clone(), OpenDocument { text, violations });
+        Ok(diagnostics)
+    }
+
+    fn lint(&self, uri: &Url, text: &str) -> Result<Vec<Violation>> {
+        // Documents not saved yet (e.g. `untitled:`) are linted with the configuration of the
+        // current directory
+        let path = uri
+            .to_file_path()
+            .unwrap_or_else(|()| PathBuf::from(uri.path()));
+        // Excluded documents are published without diagnostics, as they are never checked
+        if self.resolver.is_excluded(&path)? {
+            return Ok(vec![]);
+        }
+
+        let resolved = self.resolver.resolve(&path)?;
+        let indices = resolved.matched_overrides(&path)?;
+        let lint = resolved.config.lint.overridden(&indices)?;
+        let linter = Linter::from(&Config {
+            extends: vec![],
+            lint,
+        });
+
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path, text.to_owned())?;
+        linter.check(&doc)
+    }
+
+    fn diagnostic(index: &LineIndex<'_>, violation: &Violation) -> Diagnostic {
+        let severity = match violation.severity() {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Info => DiagnosticSeverity::INFORMATION,
+        };
+        let href = format!("{RULES_URI}#{}", violation.name().to_lowercase());
+        Diagnostic {
+            range: index.range(violation.position()),
+            severity: Some(severity),
+            code: Some(NumberOrString::String(violation.name().to_owned())),
+            code_description: Url::parse(&href).ok().map(|href| CodeDescription { href }),
+            source: Some("mado".to_owned()),
+            message: format!(
+                "{}/{} {}",
+                violation.name(),
+                violation.alias(),
+                violation.description()
+            ),
+            ..Diagnostic::default()
+        }
+    }
+
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+
+        let index = LineIndex::new(&document.text);
+        document
+            .violations
+            .iter()
+            .filter(|violation| !violation.fixes().is_empty())
+            .map(|violation| (violation, Self::diagnostic(&index, violation)))
+            .filter(|(_, diagnostic)| overlaps(&diagnostic.range, &params.range))
+            .map(|(violation, diagnostic)| {
+                let edits = violation
+                    .fixes()
+                    .iter()
+                    .map(|edit| TextEdit {
+                        range: Range::new(
+                            index.position(edit.range().start),
+                            index.position(edit.range().end),
+                        ),
+                        new_text: edit.content().to_owned(),
+                    })
+                    .collect();
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Fix {}/{}", violation.name(), violation.alias()),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), edits)])),
+                        ..WorkspaceEdit::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..CodeAction::default()
+                })
+            })
+            .collect()
+    }
+}
+
+const fn overlaps(a: &Range, b: &Range) -> bool {
+    !(is_before(a.end, b.start) || is_before(b.end, a.start))
+}
+
+const fn is_before(a: Position, b: Position) -> bool {
+    a.line < b.line || (a.line == b.line && a.character < b.character)
+}
+
+// Converts byte offsets and `Sourcepos` to positions of LSP, which count UTF-16 code units
+struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(text: &'a str) -> Self {
+        let line_starts = iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        let line_start = self.line_starts.get(line).copied().unwrap_or_default();
+        let character = self
+            .text
+            .get(line_start..offset)
+            .map_or(0, |text| text.encode_utf16().count());
+        Position::new(
+            u32::try_from(line).unwrap_or(u32::MAX),
+            u32::try_from(character).unwrap_or(u32::MAX),
+        )
+    }
+
+    // Returns the byte offset of the end of a 1-based line, excluding the line break
+    fn line_end(&self, line: usize) -> usize {
+        self.line_starts
+            .get(line)
+            .map_or(self.text.len(), |start| start - 1)
+    }
+
+    // Converts a 1-based line and byte column of Sourcepos to a byte offset
+    fn offset(&self, line: usize, column: usize) -> usize {
+        let line_start = self
+            .line_starts
+            .get(line.saturating_sub(1))
+            .copied()
+            .unwrap_or(self.text.len());
+        (line_start + column.saturating_sub(1)).min(self.line_end(line))
+    }
+
+    fn range(&self, position: &Sourcepos) -> Range {
+        let start = self.offset(position.start.line, position.start.column);
+        // The end of Sourcepos is inclusive
+        let end = (self.offset(position.end.line, position.end.column) + 1)
+            .min(self.line_end(position.end.line));
+        Range::new(self.position(start), self.position(end.max(start)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread::{self, JoinHandle};
+
+    use indoc::indoc;
+    use lsp_server::RequestId;
+    use lsp_types::notification::{Exit, Initialized};
+    use lsp_types::request::{Initialize, Shutdown};
+    use lsp_types::{
+        ClientCapabilities, CodeActionContext, DidChangeWatchedFilesClientCapabilities,
+        FileChangeType, FileEvent, InitializedParams, PartialResultParams,
+        TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
+        VersionedTextDocumentIdentifier, WorkDoneProgressParams, WorkspaceClientCapabilities,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    // A scripted client talking to a server running on another thread
+    struct Client {
+        connection: Connection,
+        server: JoinHandle<Result<()>>,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn start(config: Config, discover: bool) -> Result<Self> {
+            Self::start_with(config, discover, InitializeParams::default())
+        }
+
+        fn start_with(config: Config, discover: bool, params: InitializeParams) -> Result<Self> {
+            let mut server = LanguageServer::new(config, discover)?;
+            let (connection, server_connection) = Connection::memory();
+            let server = thread::spawn(move || server.run(&server_connection));
+            let mut client = Self {
+                connection,
+                server,
+                next_id: 0,
+            };
+            client.request(Initialize::METHOD, params)?;
+            client.notify(Initialized::METHOD, InitializedParams {})?;
+            Ok(client)
+        }
+
+        fn request(&mut self, method: &str, params: impl serde::Serialize) -> Result<Value> {
+            let response = self.send(method, params)?;
+            Ok(response.result.unwrap_or_default())
+        }
+
+        fn send(&mut self, method: &str, params: impl serde::Serialize) -> Result<Response> {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), method.to_owned(), params);
+            self.connection
+                .sender
+                .send(request.into())
+                .into_diagnostic()?;
+            match self.connection.receiver.recv().into_diagnostic()? {
+                Message::Response(response) if response.id == id => Ok(response),
+                message => Err(miette::miette!("unexpected message: {message:?}")),
+            }
+        }
+
+        fn notify(&self, method: &str, params: impl serde::Serialize) -> Result<()> {
+            let notification = Notification::new(method.to_owned(), params);
+            self.connection
+                .sender
+                .send(notification.into())
+                .into_diagnostic()
+        }
+
+        fn diagnostics(&self) -> Result<PublishDiagnosticsParams> {
+            match self.connection.receiver.recv().into_diagnostic()? {
+                Message::Notification(notification)
+                    if notification.method == PublishDiagnostics::METHOD =>
+                {
+                    serde_json::from_value(notification.params).into_diagnostic()
+                }
+                message => Err(miette::miette!("unexpected message: {message:?}")),
+            }
+        }
+
+        fn open(&self, uri: &Url, text: &str) -> Result<PublishDiagnosticsParams> {
+            let text_document =
+                TextDocumentItem::new(uri.clone(), "markdown".to_owned(), 1, text.to_owned());
+            self.notify(
+                DidOpenTextDocument::METHOD,
+                DidOpenTextDocumentParams { text_document },
+            )?;
+            self.diagnostics()
+        }
+
+        fn shutdown(mut self) -> Result<()> {
+            self.request(Shutdown::METHOD, ())?;
+            self.notify(Exit::METHOD, ())?;
+            self.server
+                .join()
+                .map_err(|_err| miette::miette!("server panicked"))?
+        }
+    }
+
+    fn uri() -> Result<Url> {
+        Url::parse("file:///tmp/test.md").into_diagnostic()
+    }
+
+    #[test]
+    fn did_open() -> Result<()> {
+        let client = Client::start(Config::default(), false)?;
+        let actual = client.open(&uri()?, "# Hello.\n")?;
+        assert_eq!(actual.uri, uri()?);
+        assert_eq!(actual.version, Some(1));
+        assert_eq!(actual.diagnostics.len(), 1);
+        let diagnostic = &actual.diagnostics[0];
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(0, 0), Position::new(0, 8))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("MD026".to_owned()))
+        );
+        assert_eq!(
+            diagnostic.message,
+            "MD026/no-trailing-punctuation Trailing punctuation in header"
+        );
+        client.shutdown()
+    }
+
+    #[test]
+    fn did_open_utf16_range() -> Result<()> {
+        let client = Client::start(Config::default(), false)?;
+        // "こんにちは" is 15 bytes in UTF-8 and 5 code units in UTF-16
+        let actual = client.open(&uri()?, "# こんにちは.\n")?;
+        assert_eq!(actual.diagnostics.len(), 1);
+        assert_eq!(
+            actual.diagnostics[0].range,
+            Range::new(Position::new(0, 0), Position::new(0, 8))
+        );
+        client.shutdown()
+    }
+
+    #[test]
+    fn did_change_and_did_close() -> Result<()> {
+        let client = Client::start(Config::default(), false)?;
+        client.open(&uri()?, "# Hello.\n")?;
+
+        client.notify(
+            DidChangeTextDocument::METHOD,
+            DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(uri()?, 2),
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: "# Hello\n".to_owned(),
+                }],
+            },
+        )?;
+        let changed = client.diagnostics()?;
+        assert_eq!(changed.version, Some(2));
+        assert_eq!(changed.diagnostics, vec![]);
+
+        client.notify(
+            DidCloseTextDocument::METHOD,
+            DidCloseTextDocumentParams {
+                text_document: TextDocumentIdentifier::new(uri()?),
+            },
+        )?;
+        let closed = client.diagnostics()?;
+        assert_eq!(closed.diagnostics, vec![]);
+        client.shutdown()
+    }
+
+    #[test]
+    fn code_action() -> Result<()> {
+        let mut client = Client::start(Config::default(), false)?;
+        let diagnostics = client.open(&uri()?, "# こんにちは.\n")?.diagnostics;
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri()?),
+            range: Range::new(Position::new(0, 3), Position::new(0, 3)),
+            context: CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let result = client.request(CodeActionRequest::METHOD, params)?;
+        let actual: Vec<CodeActionOrCommand> = serde_json::from_value(result).into_diagnostic()?;
+        let edit = TextEdit {
+            range: Range::new(Position::new(0, 7), Position::new(0, 8)),
+            new_text: String::new(),
+        };
+        let expected = vec![CodeActionOrCommand::CodeAction(CodeAction {
+            title: "Fix MD026/no-trailing-punctuation".to_owned(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(diagnostics),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(uri()?, vec![edit])])),
+                ..WorkspaceEdit::default()
+            }),
+            is_preferred: Some(true),
+            ..CodeAction::default()
+        })];
+        assert_eq!(actual, expected);
+        client.shutdown()
+    }
+
+    #[test]
+    fn did_open_with_discovered_config() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::create_dir(tmp_dir.path().join(".git")).into_diagnostic()?;
+        let config = indoc! {r#"
+            [lint.severities]
+            MD026 = "warning"
+        "#};
+        fs::write(tmp_dir.path().join("mado.toml"), config).into_diagnostic()?;
+        let path = tmp_dir.path().join("README.md");
+        let uri = Url::from_file_path(&path).map_err(|()| miette::miette!("invalid path"))?;
+
+        let client = Client::start(Config::default(), true)?;
+        let actual = client.open(&uri, "# Hello.\n")?;
+        assert_eq!(actual.diagnostics.len(), 1);
+        assert_eq!(
+            actual.diagnostics[0].severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+        client.shutdown()
+    }
+
+    #[test]
+    fn did_open_excluded() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::create_dir(tmp_dir.path().join(".git")).into_diagnostic()?;
+        let config = indoc! {r#"
+            [lint]
+            exclude = ["drafts/*.md"]
+        "#};
+        fs::write(tmp_dir.path().join("mado.toml"), config).into_diagnostic()?;
+        let path = tmp_dir.path().join("drafts").join("draft.md");
+        let uri = Url::from_file_path(&path).map_err(|()| miette::miette!("invalid path"))?;
+        let other_path = tmp_dir.path().join("README.md");
+        let other_uri =
+            Url::from_file_path(&other_path).map_err(|()| miette::miette!("invalid path"))?;
+
+        let client = Client::start(Config::default(), true)?;
+        let actual = client.open(&uri, "# Hello.\n")?;
+        assert_eq!(actual.diagnostics, vec![]);
+        let other_actual = client.open(&other_uri, "# Hello.\n")?;
+        assert_eq!(other_actual.diagnostics.len(), 1);
+        client.shutdown()
+    }
+
+    #[test]
+    fn did_change_watched_files() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::create_dir(tmp_dir.path().join(".git")).into_diagnostic()?;
+        let config_path = tmp_dir.path().join("mado.toml");
+        fs::write(&config_path, "[lint.severities]\nMD026 = \"warning\"\n").into_diagnostic()?;
+        let path = tmp_dir.path().join("README.md");
+        let uri = Url::from_file_path(&path).map_err(|()| miette::miette!("invalid path"))?;
+        let config_uri =
+            Url::from_file_path(&config_path).map_err(|()| miette::miette!("invalid path"))?;
+
+        let client = Client::start(Config::default(), true)?;
+        client.open(&uri, "# Hello.\n")?;
+
+        fs::write(&config_path, "[lint]\nrules = [\"MD001\"]\n").into_diagnostic()?;
+        client.notify(
+            DidChangeWatchedFiles::METHOD,
+            DidChangeWatchedFilesParams {
+                changes: vec![FileEvent::new(config_uri, FileChangeType::CHANGED)],
+            },
+        )?;
+        let actual = client.diagnostics()?;
+        assert_eq!(actual.uri, uri);
+        assert_eq!(actual.version, None);
+        assert_eq!(actual.diagnostics, vec![]);
+        client.shutdown()
+    }
+
+    #[test]
+    fn register_config_file_watchers() -> Result<()> {
+        let params = InitializeParams {
+            capabilities: ClientCapabilities {
+                workspace: Some(WorkspaceClientCapabilities {
+                    did_change_watched_files: Some(DidChangeWatchedFilesClientCapabilities {
+                        dynamic_registration: Some(true),
+                        relative_pattern_support: None,
+                    }),
+                    ..WorkspaceClientCapabilities::default()
+                }),
+                ..ClientCapabilities::default()
+            },
+            ..InitializeParams::default()
+        };
+        let client = Client::start_with(Config::default(), false, params)?;
+        let request = match client.connection.receiver.recv().into_diagnostic()? {
+            Message::Request(request) => request,
+            message => return Err(miette::miette!("unexpected message: {message:?}")),
+        };
+        assert_eq!(request.method, RegisterCapability::METHOD);
+        let actual: RegistrationParams =
+            serde_json::from_value(request.params).into_diagnostic()?;
+        assert_eq!(actual.registrations.len(), 1);
+        assert_eq!(
+            actual.registrations[0].method,
+            DidChangeWatchedFiles::METHOD
+        );
+
+        let response = Response::new_ok(request.id, ());
+        client
+            .connection
+            .sender
+            .send(response.into())
+            .into_diagnostic()?;
+        client.shutdown()
+    }
+
+    #[test]
+    fn unsupported_request() -> Result<()> {
+        let mut client = Client::start(Config::default(), false)?;
+        let response = client.send("textDocument/hover", ())?;
+        let actual = response.error.map(|err| err.code);
+        assert_eq!(actual, Some(ErrorCode::MethodNotFound as i32));
+        client.shutdown()
+    }
+
+    #[test]
+    fn line_index_position() {
+        let index = LineIndex::new("a\nこんにちは\n😀b");
+        assert_eq!(index.position(0), Position::new(0, 0));
+        assert_eq!(index.position(2), Position::new(1, 0));
+        assert_eq!(index.position(8), Position::new(1, 2));
+        // Offsets within a character are moved to its start
+        assert_eq!(index.position(9), Position::new(1, 2));
+        assert_eq!(index.position(22), Position::new(2, 2));
+        assert_eq!(index.position(100), Position::new(2, 3));
+    }
+}
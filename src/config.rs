@@ -1,5 +1,6 @@
+use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use etcetera::BaseStrategy as _;
 use etcetera::choose_base_strategy;
@@ -7,43 +8,80 @@ use miette::IntoDiagnostic as _;
 use miette::Result;
 use miette::miette;
 use serde::Deserialize;
+use toml::{Table, Value};
 
+mod extends;
 pub mod lint;
+pub mod migration;
 
 pub use lint::Lint;
+use migration::Migration;
 use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(default)]
 #[allow(clippy::exhaustive_structs)]
 pub struct Config {
+    /// Paths to configuration files or names of presets to inherit settings from
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
     pub lint: Lint,
 }
 
 impl Config {
     const FILE_NAME: &str = "mado.toml";
     const HIDDEN_FILE_NAME: &str = ".mado.toml";
+    // markdownlint configurations used when no configuration file of mado exists
+    const MARKDOWNLINT_FILE_NAMES: [&str; 4] = [
+        ".markdownlint.jsonc",
+        ".markdownlint.json",
+        ".markdownlint.yaml",
+        ".markdownlint.yml",
+    ];
 
+    /// Loads `mado.toml`, or a markdownlint JSON/YAML configuration translated on the fly.
+    /// Also returns warnings about markdownlint options that are not enforced
     #[inline]
-    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let config_text = fs::read_to_string(path).into_diagnostic()?;
-        toml::from_str(&config_text).map_err(|err| miette!(err))
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<(Self, Vec<String>)> {
+        let path = path.as_ref();
+        if Self::is_markdownlint(path) {
+            let migration = Migration::load(path)?;
+            let warnings = migration
+                .warnings()
+                .iter()
+                .map(|warning| format!("{warning} in {}", path.display()))
+                .collect();
+            return Ok((migration.to_config()?, warnings));
+        }
+
+        let table = extends::load(path)?;
+        let config = table.try_into().map_err(|err| miette!(err))?;
+        Ok((config, vec![]))
     }
 
+    /// Returns the files extended by the configuration file at `path`, recursively
     #[inline]
-    pub fn resolve() -> Result<Self> {
-        let local_path = Path::new(Self::FILE_NAME);
-        let exists_local = fs::exists(local_path).into_diagnostic()?;
-        if exists_local {
-            return Self::load(local_path);
+    pub fn bases(path: &Path) -> Result<Vec<PathBuf>> {
+        if Self::is_markdownlint(path) {
+            return Ok(vec![]);
         }
 
-        let hidden_local_path = Path::new(Self::HIDDEN_FILE_NAME);
-        let exists_hidden_local = fs::exists(hidden_local_path).into_diagnostic()?;
-        if exists_hidden_local {
-            return Self::load(hidden_local_path);
+        extends::bases(path)
+    }
+
+    #[inline]
+    pub fn resolve() -> Result<(Self, Vec<String>)> {
+        let current_dir = env::current_dir().into_diagnostic()?;
+        if let Some(path) = Self::find_nearest(&current_dir)? {
+            return Self::load(path);
         }
 
+        Self::resolve_global()
+    }
+
+    /// Loads the configuration from the user config directory, or returns the default one
+    #[inline]
+    pub fn resolve_global() -> Result<(Self, Vec<String>)> {
         let strategy = choose_base_strategy().into_diagnostic()?;
         let config_path = strategy.config_dir().join("mado").join(Self::FILE_NAME);
         let exists_config = fs::exists(&config_path).into_diagnostic()?;
@@ -51,7 +89,81 @@ impl Config {
             return Self::load(&config_path);
         }
 
-        Ok(Self::default())
+        Ok((Self::default(), vec![]))
+    }
+
+    /// Returns the path of the configuration file in `dir`, if any.
+    /// A markdownlint configuration is returned only if no `mado.toml` exists
+    #[inline]
+    pub fn find(dir: &Path) -> Result<Option<PathBuf>> {
+        for file_name in Self::file_names() {
+            let path = dir.join(file_name);
+            if fs::exists(&path).into_diagnostic()? {
+                return Ok(Some(path));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the path of the nearest configuration file, looking up from `dir`
+    /// to the repository root (or the filesystem root outside repositories)
+    #[inline]
+    pub fn find_nearest(dir: &Path) -> Result<Option<PathBuf>> {
+        for ancestor in dir.ancestors() {
+            if let Some(path) = Self::find(ancestor)? {
+                return Ok(Some(path));
+            }
+
+            if Self::is_root(ancestor)? {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns true if `path` has the name of a configuration file found by [`Config::find`]
+    #[inline]
+    #[must_use]
+    pub fn is_config_file(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| Self::file_names().any(|file_name| name == file_name))
+    }
+
+    /// Returns true if `dir` is the root of a repository, where the lookup stops
+    #[inline]
+    pub fn is_root(dir: &Path) -> Result<bool> {
+        fs::exists(dir.join(".git")).into_diagnostic()
+    }
+
+    /// Returns the names of configuration files found by [`Config::find`]
+    #[inline]
+    pub fn file_names() -> impl Iterator<Item = &'static str> {
+        [Self::FILE_NAME, Self::HIDDEN_FILE_NAME]
+            .into_iter()
+            .chain(Self::MARKDOWNLINT_FILE_NAMES)
+    }
+
+    fn is_markdownlint(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("json" | "jsonc" | "yaml" | "yml")
+        )
+    }
+}
+
+// Merges `partial` into `base` recursively, where values in `partial` take precedence
+pub(crate) fn merge_tables(base: &mut Table, partial: &Table) {
+    for (key, value) in partial {
+        match (base.get_mut(key), value) {
+            (Some(Value::Table(base_table)), Value::Table(partial_table)) => {
+                merge_tables(base_table, partial_table);
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
     }
 }
 
@@ -63,12 +175,15 @@ mod tests {
     use indoc::indoc;
     use lint::{MD002, RuleSet};
     use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
 
     #[test]
     fn load() -> Result<()> {
         let path = Path::new("mado.toml");
-        let actual = Config::load(path)?;
+        let (actual, warnings) = Config::load(path)?;
+        assert!(warnings.is_empty());
         let mut expected = Config::default();
+        expected.lint.output_format = Some(Format::Concise);
         expected.lint.md013.code_blocks = false;
         expected.lint.md013.tables = false;
         expected.lint.md024.allow_different_nesting = true;
@@ -76,6 +191,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn load_markdownlint() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join(".markdownlint.json");
+        let text = indoc! {r#"
+            {
+              "default": false,
+              "heading-increment": true,
+              "line-length": { "line_length": 100 },
+              "link-fragments": true
+            }
+        "#};
+        fs::write(&path, text).into_diagnostic()?;
+
+        let (actual, warnings) = Config::load(&path)?;
+        assert_eq!(
+            warnings,
+            vec![format!(
+                "rule `link-fragments` is not supported in {}",
+                path.display()
+            )]
+        );
+        let mut expected = Config::default();
+        expected.lint.rules = vec![RuleSet::MD001, RuleSet::MD013];
+        expected.lint.md013.line_length = 100;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
     #[test]
     fn resolve() -> Result<()> {
         let actual = Config::resolve()?;
@@ -85,6 +229,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        assert_eq!(Config::find(tmp_dir.path())?, None);
+
+        let markdownlint_path = tmp_dir.path().join(".markdownlint.yaml");
+        fs::write(&markdownlint_path, "").into_diagnostic()?;
+        assert_eq!(Config::find(tmp_dir.path())?, Some(markdownlint_path));
+
+        let hidden_path = tmp_dir.path().join(".mado.toml");
+        fs::write(&hidden_path, "").into_diagnostic()?;
+        assert_eq!(Config::find(tmp_dir.path())?, Some(hidden_path));
+
+        let path = tmp_dir.path().join("mado.toml");
+        fs::write(&path, "").into_diagnostic()?;
+        assert_eq!(Config::find(tmp_dir.path())?, Some(path));
+        Ok(())
+    }
+
+    #[test]
+    fn is_config_file() {
+        assert!(Config::is_config_file(Path::new("mado.toml")));
+        assert!(Config::is_config_file(Path::new("docs/.mado.toml")));
+        assert!(Config::is_config_file(Path::new(
+            "/repo/.markdownlint.jsonc"
+        )));
+        assert!(!Config::is_config_file(Path::new("README.md")));
+        assert!(!Config::is_config_file(Path::new("mado.toml/README.md")));
+    }
+
+    #[test]
+    fn find_nearest() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let repo_dir = tmp_dir.path().join("repo");
+        let package_dir = repo_dir.join("packages").join("foo");
+        fs::create_dir_all(repo_dir.join(".git")).into_diagnostic()?;
+        fs::create_dir_all(&package_dir).into_diagnostic()?;
+
+        // Configurations outside the repository are ignored
+        fs::write(tmp_dir.path().join("mado.toml"), "").into_diagnostic()?;
+        assert_eq!(Config::find_nearest(&package_dir)?, None);
+
+        let path = repo_dir.join("mado.toml");
+        fs::write(&path, "").into_diagnostic()?;
+        assert_eq!(Config::find_nearest(&package_dir)?, Some(path));
+
+        let package_path = package_dir.join(".mado.toml");
+        fs::write(&package_path, "").into_diagnostic()?;
+        assert_eq!(Config::find_nearest(&package_dir)?, Some(package_path));
+        Ok(())
+    }
+
     #[test]
     fn deserialize() -> Result<()> {
         let text = indoc! {r#"
@@ -92,12 +288,16 @@ mod tests {
             output-format = "mdl"
             rules = ["MD027"]
 
+            [lint.reporters]
+            json-lines = "mado.jsonl"
+
             [lint.md002]
             level = 2
         "#};
         let actual: Config = toml::from_str(text).into_diagnostic()?;
         let mut expected = Config::default();
-        expected.lint.output_format = Format::Mdl;
+        expected.lint.output_format = Some(Format::Mdl);
+        expected.lint.reporters = vec![(Format::JsonLines, PathBuf::from("mado.jsonl"))];
         expected.lint.rules = vec![RuleSet::MD027];
         expected.lint.md002 = MD002 { level: 2 };
         assert_eq!(actual, expected);
@@ -0,0 +1,331 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{IntoDiagnostic as _, LabeledSpan, NamedSource, Result, miette};
+use serde::Deserialize;
+use toml::{Spanned, Table, Value, de};
+
+use super::{Config, merge_tables};
+
+const PRESETS: [(&str, &str); 2] = [
+    ("mado:recommended", include_str!("presets/recommended.toml")),
+    ("mado:relaxed", include_str!("presets/relaxed.toml")),
+];
+
+#[derive(Deserialize)]
+struct Extends {
+    #[serde(default)]
+    extends: Vec<Spanned<String>>,
+}
+
+/// Returns the names of built-in presets that can be extended
+#[inline]
+pub fn presets() -> impl Iterator<Item = &'static str> {
+    PRESETS.iter().map(|(name, _)| *name)
+}
+
+/// Loads the configuration table at `path` merged into the ones it extends
+pub fn load(path: &Path) -> Result<Table> {
+    let mut chain = vec![];
+    load_file(path, &mut chain)
+}
+
+/// Returns the files extended by the configuration file at `path`, recursively
+pub fn bases(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut bases = vec![];
+    collect_bases(path, &mut bases)?;
+    Ok(bases)
+}
+
+fn collect_bases(path: &Path, bases: &mut Vec<PathBuf>) -> Result<()> {
+    let text = fs::read_to_string(path).into_diagnostic()?;
+    let extends: Extends = toml::from_str(&text).into_diagnostic()?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    for entry in extends.extends {
+        if entry.get_ref().starts_with("mado:") {
+            continue;
+        }
+
+        // Cycles are reported on loading, so each file is visited only once here
+        let base = fs::canonicalize(dir.join(entry.get_ref())).into_diagnostic()?;
+        if !bases.contains(&base) {
+            bases.push(base.clone());
+            collect_bases(&base, bases)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn load_file(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Table> {
+    let text = fs::read_to_string(path)
+        .into_diagnostic()
+        .map_err(|err| err.wrap_err(format!("Failed to read {}", path.display())))?;
+    chain.push(fs::canonicalize(path).into_diagnostic()?);
+    let table = load_text(&path.display().to_string(), &text, path.parent(), chain)?;
+    chain.pop();
+    Ok(table)
+}
+
+fn load_text(
+    name: &str,
+    text: &str,
+    dir: Option<&Path>,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Table> {
+    let to_report = |err: de::Error| {
+        let labels = err
+            .span()
+            .map(|span| LabeledSpan::at(span, err.message()))
+            .into_iter()
+            .collect::<Vec<_>>();
+        miette!(labels = labels, "Invalid configuration in {name}")
+            .with_source_code(NamedSource::new(name, text.to_owned()))
+    };
+
+    // Validate each file on its own so that errors point at the invalid one
+    toml::from_str::<Config>(text).map_err(to_report)?;
+    let mut table: Table = toml::from_str(text).map_err(to_report)?;
+    let extends: Extends = toml::from_str(text).map_err(to_report)?;
+    table.remove("extends");
+
+    let mut merged = Table::new();
+    for entry in extends.extends {
+        let error = |message: String, label: &str| {
+            miette!(
+                labels = vec![LabeledSpan::at(entry.span(), label)],
+                "{message}"
+            )
+            .with_source_code(NamedSource::new(name, text.to_owned()))
+        };
+
+        let base = if let Some((_, preset)) = PRESETS
+            .iter()
+            .find(|(preset_name, _)| preset_name == entry.get_ref())
+        {
+            load_text(entry.get_ref(), preset, None, chain)?
+        } else if entry.get_ref().starts_with("mado:") {
+            let names: Vec<_> = presets().collect();
+            return Err(error(
+                format!("Unknown preset `{}` in {name}", entry.get_ref()),
+                &format!("expected one of {}", names.join(", ")),
+            ));
+        } else {
+            let path = dir.unwrap_or_else(|| Path::new(".")).join(entry.get_ref());
+            let Ok(canonical_path) = fs::canonicalize(&path) else {
+                return Err(error(
+                    format!("Failed to extend {} from {name}", path.display()),
+                    "file not found",
+                ));
+            };
+
+            if chain.contains(&canonical_path) {
+                let cycle: Vec<_> = chain
+                    .iter()
+                    .skip_while(|ancestor| **ancestor != canonical_path)
+                    .chain([&canonical_path])
+                    .map(|ancestor| ancestor.display().to_string())
+                    .collect();
+                return Err(error(
+                    format!("Cyclic extends in {name}: {}", cycle.join(" -> ")),
+                    "extends a configuration extending this file",
+                ));
+            }
+
+            load_file(&path, chain)?
+        };
+
+        merge(&mut merged, base);
+    }
+
+    merge(&mut merged, table);
+    Ok(merged)
+}
+
+// Merges `config` into `base`, where lint settings are merged as follows:
+//
+// * `exclude` and `overrides` are concatenated
+// * Rule settings (e.g. `md013`) are merged recursively
+// * Other values, including `rules`, are replaced
+fn merge(base: &mut Table, config: Table) {
+    for (key, value) in config {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_lint)), Value::Table(lint)) if key == "lint" => {
+                merge_lint(base_lint, lint);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn merge_lint(base: &mut Table, lint: Table) {
+    for (key, value) in lint {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Array(base_values)), Value::Array(values))
+                if key == "exclude" || key == "overrides" =>
+            {
+                for item in values {
+                    if !base_values.contains(&item) {
+                        base_values.push(item);
+                    }
+                }
+            }
+            (Some(Value::Table(base_table)), Value::Table(table)) => {
+                merge_tables(base_table, &table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::config::lint::RuleSet;
+
+    #[test]
+    fn load_with_extends() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let shared_dir = tmp_dir.path().join("shared");
+        let repo_dir = tmp_dir.path().join("repo");
+        fs::create_dir(&shared_dir).into_diagnostic()?;
+        fs::create_dir(&repo_dir).into_diagnostic()?;
+        let base = indoc! {r#"
+            extends = ["mado:recommended"]
+
+            [lint]
+            exclude = ["CHANGELOG.md"]
+            rules = ["MD001", "MD013"]
+
+            [lint.md013]
+            line-length = 100
+        "#};
+        fs::write(shared_dir.join("mado.toml"), base).into_diagnostic()?;
+        let config = indoc! {r#"
+            extends = ["../shared/mado.toml"]
+
+            [lint]
+            exclude = ["vendor/**", "CHANGELOG.md"]
+            rules = ["MD013"]
+
+            [lint.md013]
+            tables = true
+        "#};
+        let path = repo_dir.join("mado.toml");
+        fs::write(&path, config).into_diagnostic()?;
+
+        let actual: Config = load(&path)?.try_into().into_diagnostic()?;
+        let mut expected = Config::default();
+        expected.lint.exclude = vec![
+            globset::Glob::new("CHANGELOG.md").into_diagnostic()?,
+            globset::Glob::new("vendor/**").into_diagnostic()?,
+        ];
+        expected.lint.rules = vec![RuleSet::MD013];
+        expected.lint.md013.line_length = 100;
+        expected.lint.md013.code_blocks = false;
+        expected.lint.md013.tables = true;
+        expected.lint.md024.allow_different_nesting = true;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn load_presets() -> Result<()> {
+        for preset in presets() {
+            let table = load_text(
+                preset,
+                &format!("extends = [{preset:?}]"),
+                None,
+                &mut vec![],
+            )?;
+            let config: Config = table.try_into().into_diagnostic()?;
+            assert!(!config.lint.rules.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn load_with_cyclic_extends() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let a_path = tmp_dir.path().join("a.toml");
+        let b_path = tmp_dir.path().join("b.toml");
+        fs::write(&a_path, "extends = ['b.toml']").into_diagnostic()?;
+        fs::write(&b_path, "extends = ['a.toml']").into_diagnostic()?;
+
+        let err = load(&a_path)
+            .err()
+            .ok_or_else(|| miette!("must be an error"))?;
+        let root_cause = err.chain().last().map(ToString::to_string);
+        let a = fs::canonicalize(&a_path).into_diagnostic()?;
+        let b = fs::canonicalize(&b_path).into_diagnostic()?;
+        assert_eq!(
+            root_cause,
+            Some(format!(
+                "Cyclic extends in {}: {} -> {} -> {}",
+                b_path.display(),
+                a.display(),
+                b.display(),
+                a.display()
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn load_with_invalid_base() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("mado.toml");
+        let base_path = tmp_dir.path().join("base.toml");
+        fs::write(&path, "extends = ['base.toml']").into_diagnostic()?;
+        fs::write(&base_path, "[lint]\nquiet = 1\n").into_diagnostic()?;
+
+        let err = load(&path)
+            .err()
+            .ok_or_else(|| miette!("must be an error"))?;
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid configuration in {}", base_path.display())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn load_with_unknown_preset() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("mado.toml");
+        fs::write(&path, "extends = ['mado:unknown']").into_diagnostic()?;
+
+        let err = load(&path)
+            .err()
+            .ok_or_else(|| miette!("must be an error"))?;
+        assert_eq!(
+            err.to_string(),
+            format!("Unknown preset `mado:unknown` in {}", path.display())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bases() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("mado.toml");
+        let a_path = tmp_dir.path().join("a.toml");
+        let b_path = tmp_dir.path().join("b.toml");
+        fs::write(&path, "extends = ['mado:recommended', 'a.toml', 'b.toml']").into_diagnostic()?;
+        fs::write(&a_path, "extends = ['b.toml']").into_diagnostic()?;
+        fs::write(&b_path, "").into_diagnostic()?;
+
+        let a = fs::canonicalize(&a_path).into_diagnostic()?;
+        let b = fs::canonicalize(&b_path).into_diagnostic()?;
+        assert_eq!(super::bases(&path)?, vec![a, b]);
+        Ok(())
+    }
+}
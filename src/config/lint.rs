@@ -1,8 +1,17 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::slice;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use miette::{IntoDiagnostic as _, Result};
-use serde::{Deserialize, Serialize};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{output::Format, rule, rule::Rule};
+use crate::output::Format;
+use crate::rule::{self, Metadata, Rule, Severity};
 
 mod md002;
 mod md003;
@@ -19,6 +28,7 @@ mod md035;
 mod md036;
 mod md041;
 mod md046;
+mod overrides;
 
 pub use md002::MD002;
 pub use md003::MD003;
@@ -35,17 +45,37 @@ pub use md035::MD035;
 pub use md036::MD036;
 pub use md041::MD041;
 pub use md046::MD046;
+pub use overrides::Override;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default, rename_all = "kebab-case")]
 #[allow(clippy::exhaustive_structs)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Lint {
     pub respect_ignore: bool,
     pub respect_gitignore: bool,
-    pub output_format: Format,
+    /// Defaults to `github` on GitHub Actions and `concise` otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<Format>,
+    /// Files to write reports of other formats to, in addition to the output
+    #[serde(skip_serializing_if = "Vec::is_empty", with = "reporters")]
+    pub reporters: Vec<(Format, PathBuf)>,
     pub quiet: bool,
+    /// Fail when there are more warnings than this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_warnings: Option<usize>,
+    pub fix: bool,
+    pub report_unused_suppressions: bool,
+    /// Reuse violations of unchanged files stored in `.mado_cache`
+    pub cache: bool,
+    /// File of existing violations that are not reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<PathBuf>,
     pub exclude: Vec<Glob>,
     pub rules: Vec<RuleSet>,
+    pub overrides: Vec<Override>,
+    /// Severities of rules or tags, which default to `error`
+    pub severities: BTreeMap<RuleSet, Severity>,
     pub md002: MD002,
     pub md003: MD003,
     pub md004: MD004,
@@ -66,22 +96,44 @@ pub struct Lint {
 impl Lint {
     #[inline]
     pub fn exclude_set(&self) -> Result<GlobSet> {
+        Self::glob_set(&self.exclude)
+    }
+
+    /// Returns a set matching files for each of `overrides`
+    #[inline]
+    pub fn override_sets(&self) -> Result<Vec<GlobSet>> {
+        self.overrides.iter().map(Override::file_set).collect()
+    }
+
+    /// Returns the lint configuration with `overrides` at `indices` applied in order
+    #[inline]
+    pub fn overridden(&self, indices: &[usize]) -> Result<Self> {
+        indices
+            .iter()
+            .filter_map(|&index| self.overrides.get(index))
+            .try_fold(self.clone(), |lint, overrides| overrides.apply(&lint))
+    }
+
+    fn glob_set(globs: &[Glob]) -> Result<GlobSet> {
         let mut builder = GlobSetBuilder::new();
-        for glob in &self.exclude {
+        for glob in globs {
             // Strip a leading "./" so that e.g. "file.md" and "./file.md" are
             // treated as the same pattern (see issue #168). Keep this in sync
-            // with the walked-path normalization in
-            // MarkdownLintVisitor::visit_inner (src/service/visitor.rs) or the
-            // two sides stop agreeing on what a match is.
+            // with the walked-path normalization in normalize_path
+            // (src/service/visitor.rs) or the two sides stop agreeing on what a
+            // match is.
             let pattern = glob.glob().trim_start_matches("./");
             builder.add(Glob::new(pattern).into_diagnostic()?);
         }
         builder.build().into_diagnostic()
     }
 
-    fn flatten_rules(&self) -> Vec<RuleSet> {
-        let mut flatten: Vec<_> = self
-            .rules
+    pub(crate) fn flatten_rules(&self) -> Vec<RuleSet> {
+        Self::flatten(&self.rules)
+    }
+
+    pub(crate) fn flatten(rules: &[RuleSet]) -> Vec<RuleSet> {
+        let mut flatten: Vec<_> = rules
             .iter()
             .flat_map(|rule| match rule {
                 // TODO: Use rule::Metadata#tags
@@ -173,6 +225,67 @@ impl Lint {
         flatten.dedup();
         flatten
     }
+
+    /// Returns the severity of each rule by name. Rules take precedence over tags
+    pub(crate) fn rule_severities(&self) -> BTreeMap<&'static str, Severity> {
+        let mut severities = BTreeMap::new();
+        // Tags are ordered after rules, so that rules are applied last
+        for (rules, severity) in self.severities.iter().rev() {
+            for rule in Self::flatten(slice::from_ref(rules)) {
+                if let Some(name) = rule.name() {
+                    severities.insert(name, *severity);
+                }
+            }
+        }
+        severities
+    }
+
+    // Returns `None` for rules reported for suppression comments, which have no checks
+    fn to_rule(&self, rule: &RuleSet) -> Option<Rule> {
+        let checked_rule = match rule {
+            RuleSet::MD001 => Rule::MD001(rule::MD001::new()),
+            RuleSet::MD002 => Rule::MD002(rule::MD002::from(&self.md002)),
+            RuleSet::MD003 => Rule::MD003(rule::MD003::from(&self.md003)),
+            RuleSet::MD004 => Rule::MD004(rule::MD004::from(&self.md004)),
+            RuleSet::MD005 => Rule::MD005(rule::MD005::new()),
+            RuleSet::MD006 => Rule::MD006(rule::MD006::new()),
+            RuleSet::MD007 => Rule::MD007(rule::MD007::from(&self.md007)),
+            RuleSet::MD009 => Rule::MD009(rule::MD009::new()),
+            RuleSet::MD010 => Rule::MD010(rule::MD010::new()),
+            RuleSet::MD012 => Rule::MD012(rule::MD012::new()),
+            RuleSet::MD013 => Rule::MD013(rule::MD013::from(&self.md013)),
+            RuleSet::MD014 => Rule::MD014(rule::MD014::new()),
+            RuleSet::MD018 => Rule::MD018(rule::MD018::new()),
+            RuleSet::MD019 => Rule::MD019(rule::MD019::new()),
+            RuleSet::MD020 => Rule::MD020(rule::MD020::new()),
+            RuleSet::MD021 => Rule::MD021(rule::MD021::new()),
+            RuleSet::MD022 => Rule::MD022(rule::MD022::new()),
+            RuleSet::MD023 => Rule::MD023(rule::MD023::new()),
+            RuleSet::MD024 => Rule::MD024(rule::MD024::from(&self.md024)),
+            RuleSet::MD025 => Rule::MD025(rule::MD025::from(&self.md025)),
+            RuleSet::MD026 => Rule::MD026(rule::MD026::from(&self.md026)),
+            RuleSet::MD027 => Rule::MD027(rule::MD027::new()),
+            RuleSet::MD028 => Rule::MD028(rule::MD028::new()),
+            RuleSet::MD029 => Rule::MD029(rule::MD029::from(&self.md029)),
+            RuleSet::MD030 => Rule::MD030(rule::MD030::from(&self.md030)),
+            RuleSet::MD031 => Rule::MD031(rule::MD031::new()),
+            RuleSet::MD032 => Rule::MD032(rule::MD032::new()),
+            RuleSet::MD033 => Rule::MD033(rule::MD033::from(&self.md033)),
+            RuleSet::MD034 => Rule::MD034(rule::MD034::new()),
+            RuleSet::MD035 => Rule::MD035(rule::MD035::from(&self.md035)),
+            RuleSet::MD036 => Rule::MD036(rule::MD036::from(&self.md036)),
+            RuleSet::MD037 => Rule::MD037(rule::MD037::new()),
+            RuleSet::MD038 => Rule::MD038(rule::MD038::new()),
+            RuleSet::MD039 => Rule::MD039(rule::MD039::new()),
+            RuleSet::MD040 => Rule::MD040(rule::MD040::new()),
+            RuleSet::MD041 => Rule::MD041(rule::MD041::from(&self.md041)),
+            RuleSet::MD046 => Rule::MD046(rule::MD046::from(&self.md046)),
+            RuleSet::MD047 => Rule::MD047(rule::MD047::new()),
+            RuleSet::UnusedSuppression | RuleSet::UnknownSuppression => return None,
+            _ => unreachable!("tags are flatten"),
+        };
+        Some(checked_rule)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -216,6 +329,10 @@ pub enum RuleSet {
     MD041,
     MD046,
     MD047,
+    #[serde(rename = "unused-suppression")]
+    UnusedSuppression,
+    #[serde(rename = "unknown-suppression")]
+    UnknownSuppression,
     #[serde(rename = "atx")]
     Atx,
     #[serde(rename = "atx-closed")]
@@ -258,14 +375,76 @@ pub enum RuleSet {
     Whitespace,
 }
 
+// Lowercase names and aliases of all rules to their names (e.g. "line-length" to "MD013")
+static RULE_NAMES: LazyLock<FxHashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut rule_names = FxHashMap::default();
+    for rule in Vec::<Rule>::from(&Lint::default()) {
+        let metadata = rule.metadata();
+        rule_names.insert(metadata.name.to_ascii_lowercase(), metadata.name);
+        for alias in metadata.aliases {
+            rule_names.insert(alias.to_ascii_lowercase(), metadata.name);
+        }
+    }
+    rule_names
+});
+
+impl RuleSet {
+    /// Returns the name of the rule with the given name or alias, ignoring case
+    #[inline]
+    #[must_use]
+    pub fn rule_name(name_or_alias: &str) -> Option<&'static str> {
+        RULE_NAMES.get(&name_or_alias.to_ascii_lowercase()).copied()
+    }
+
+    /// Returns the name of violations reported by this rule, or `None` for tags
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::UnusedSuppression => Some(Metadata::UNUSED_SUPPRESSION.name),
+            Self::UnknownSuppression => Some(Metadata::UNKNOWN_SUPPRESSION.name),
+            _ => {
+                let name = toml::Value::try_from(self).ok()?;
+                Self::rule_name(name.as_str()?)
+            }
+        }
+    }
+}
+
+// Reporters are a table in `mado.toml`, while the command line can write
+// several files of the same format
+mod reporters {
+    use super::{BTreeMap, Deserialize as _, Deserializer, Format, PathBuf, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        reporters: &[(Format, PathBuf)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(reporters.iter().map(|(format, path)| (format, path)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(Format, PathBuf)>, D::Error> {
+        let reporters = BTreeMap::<Format, PathBuf>::deserialize(deserializer)?;
+        Ok(reporters.into_iter().collect())
+    }
+}
+
 impl Default for Lint {
     #[inline]
     fn default() -> Self {
         Self {
             respect_ignore: true,
             respect_gitignore: true,
-            output_format: Format::Concise,
+            output_format: None,
+            reporters: vec![],
             quiet: false,
+            max_warnings: None,
+            fix: false,
+            report_unused_suppressions: false,
+            cache: true,
+            baseline: None,
             exclude: vec![],
             rules: vec![
                 RuleSet::MD001,
@@ -307,6 +486,8 @@ impl Default for Lint {
                 RuleSet::MD046,
                 RuleSet::MD047,
             ],
+            overrides: vec![],
+            severities: BTreeMap::new(),
             md002: MD002::default(),
             md003: MD003::default(),
             md004: MD004::default(),
@@ -332,58 +513,28 @@ impl From<&Lint> for Vec<Rule> {
         config
             .flatten_rules()
             .iter()
-            .map(|rule| match rule {
-                RuleSet::MD001 => Rule::MD001(rule::MD001::new()),
-                RuleSet::MD002 => Rule::MD002(rule::MD002::from(&config.md002)),
-                RuleSet::MD003 => Rule::MD003(rule::MD003::from(&config.md003)),
-                RuleSet::MD004 => Rule::MD004(rule::MD004::from(&config.md004)),
-                RuleSet::MD005 => Rule::MD005(rule::MD005::new()),
-                RuleSet::MD006 => Rule::MD006(rule::MD006::new()),
-                RuleSet::MD007 => Rule::MD007(rule::MD007::from(&config.md007)),
-                RuleSet::MD009 => Rule::MD009(rule::MD009::new()),
-                RuleSet::MD010 => Rule::MD010(rule::MD010::new()),
-                RuleSet::MD012 => Rule::MD012(rule::MD012::new()),
-                RuleSet::MD013 => Rule::MD013(rule::MD013::from(&config.md013)),
-                RuleSet::MD014 => Rule::MD014(rule::MD014::new()),
-                RuleSet::MD018 => Rule::MD018(rule::MD018::new()),
-                RuleSet::MD019 => Rule::MD019(rule::MD019::new()),
-                RuleSet::MD020 => Rule::MD020(rule::MD020::new()),
-                RuleSet::MD021 => Rule::MD021(rule::MD021::new()),
-                RuleSet::MD022 => Rule::MD022(rule::MD022::new()),
-                RuleSet::MD023 => Rule::MD023(rule::MD023::new()),
-                RuleSet::MD024 => Rule::MD024(rule::MD024::from(&config.md024)),
-                RuleSet::MD025 => Rule::MD025(rule::MD025::from(&config.md025)),
-                RuleSet::MD026 => Rule::MD026(rule::MD026::from(&config.md026)),
-                RuleSet::MD027 => Rule::MD027(rule::MD027::new()),
-                RuleSet::MD028 => Rule::MD028(rule::MD028::new()),
-                RuleSet::MD029 => Rule::MD029(rule::MD029::from(&config.md029)),
-                RuleSet::MD030 => Rule::MD030(rule::MD030::from(&config.md030)),
-                RuleSet::MD031 => Rule::MD031(rule::MD031::new()),
-                RuleSet::MD032 => Rule::MD032(rule::MD032::new()),
-                RuleSet::MD033 => Rule::MD033(rule::MD033::from(&config.md033)),
-                RuleSet::MD034 => Rule::MD034(rule::MD034::new()),
-                RuleSet::MD035 => Rule::MD035(rule::MD035::from(&config.md035)),
-                RuleSet::MD036 => Rule::MD036(rule::MD036::from(&config.md036)),
-                RuleSet::MD037 => Rule::MD037(rule::MD037::new()),
-                RuleSet::MD038 => Rule::MD038(rule::MD038::new()),
-                RuleSet::MD039 => Rule::MD039(rule::MD039::new()),
-                RuleSet::MD040 => Rule::MD040(rule::MD040::new()),
-                RuleSet::MD041 => Rule::MD041(rule::MD041::from(&config.md041)),
-                RuleSet::MD046 => Rule::MD046(rule::MD046::from(&config.md046)),
-                RuleSet::MD047 => Rule::MD047(rule::MD047::new()),
-                _ => unreachable!("tags are flatten"),
-            })
+            .filter_map(|rule| config.to_rule(rule))
             .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rule::Tag;
 
     use super::*;
 
+    #[test]
+    fn rule_name() {
+        assert_eq!(RuleSet::rule_name("MD013"), Some("MD013"));
+        assert_eq!(RuleSet::rule_name("md013"), Some("MD013"));
+        assert_eq!(RuleSet::rule_name("Line-Length"), Some("MD013"));
+        assert_eq!(RuleSet::rule_name("headers"), None);
+        assert_eq!(RuleSet::rule_name("MD999"), None);
+    }
+
     #[test]
     fn exclude_set() -> Result<()> {
         let config = Lint {
@@ -416,6 +567,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn overridden() -> Result<()> {
+        let config: Lint = toml::from_str(indoc! {"
+            [[overrides]]
+            files = ['*.md']
+            disable-rules = ['MD001']
+
+            [[overrides]]
+            files = ['CHANGELOG.md']
+            rules = ['MD013']
+            extend-rules = ['MD001']
+        "})
+        .into_diagnostic()?;
+
+        assert_eq!(config.overridden(&[])?, config);
+        let mut actual = config.overridden(&[0])?.rules;
+        assert!(!actual.contains(&RuleSet::MD001));
+        actual = config.overridden(&[0, 1])?.rules;
+        assert_eq!(actual, vec![RuleSet::MD013, RuleSet::MD001]);
+        Ok(())
+    }
+
+    #[test]
+    fn rule_severities() {
+        let mut lint = Lint::default();
+        lint.severities.insert(RuleSet::MD026, Severity::Warning);
+        lint.severities.insert(RuleSet::Headers, Severity::Info);
+        lint.severities
+            .insert(RuleSet::UnusedSuppression, Severity::Warning);
+        let actual = lint.rule_severities();
+        assert_eq!(actual.get("MD001"), Some(&Severity::Info));
+        assert_eq!(actual.get("MD026"), Some(&Severity::Warning));
+        assert_eq!(actual.get("MD013"), None);
+        assert_eq!(actual.get("unused-suppression"), Some(&Severity::Warning));
+        assert_eq!(actual.get("unknown-suppression"), None);
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(RuleSet::MD013.name(), Some("MD013"));
+        assert_eq!(
+            RuleSet::UnusedSuppression.name(),
+            Some("unused-suppression")
+        );
+        assert_eq!(RuleSet::Headers.name(), None);
+    }
+
+    #[test]
+    fn from_lint_for_vec_rule_without_checks() {
+        let config = Lint {
+            rules: vec![RuleSet::MD001, RuleSet::UnknownSuppression],
+            ..Lint::default()
+        };
+        assert_eq!(Vec::from(&config), vec![Rule::MD001(rule::MD001::new())]);
+    }
+
     #[test]
     fn from_lint_for_vec_rule() {
         let config = Lint::default();
@@ -0,0 +1,145 @@
+use globset::{Glob, GlobSet};
+use miette::{IntoDiagnostic as _, Result, miette};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use super::{Lint, RuleSet};
+use crate::config::merge_tables;
+
+/// Lint settings applied only to files matching `files`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(clippy::exhaustive_structs)]
+pub struct Override {
+    pub files: Vec<Glob>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<RuleSet>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extend_rules: Vec<RuleSet>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable_rules: Vec<RuleSet>,
+    /// Partial rule settings (e.g. `md013`) merged into the base ones
+    #[serde(flatten)]
+    pub settings: Table,
+}
+
+impl Override {
+    #[inline]
+    pub fn file_set(&self) -> Result<GlobSet> {
+        Lint::glob_set(&self.files)
+    }
+
+    /// Returns `lint` with this override applied
+    #[inline]
+    pub fn apply(&self, lint: &Lint) -> Result<Lint> {
+        let mut table = Table::try_from(lint).into_diagnostic()?;
+        table.remove("overrides");
+        for (key, value) in &self.settings {
+            match (table.get_mut(key), value) {
+                (Some(Value::Table(base)), Value::Table(partial)) => merge_tables(base, partial),
+                (Some(Value::Table(_)), _) => {
+                    return Err(miette!("`{key}` in overrides must be a table"));
+                }
+                _ => return Err(miette!("`{key}` cannot be overridden per file")),
+            }
+        }
+
+        let mut overridden: Lint = table.try_into().map_err(|err| miette!(err))?;
+        if let Some(rules) = &self.rules {
+            overridden.rules.clone_from(rules);
+        }
+        overridden.rules.extend(self.extend_rules.iter().cloned());
+        if !self.disable_rules.is_empty() {
+            let disabled = Lint::flatten(&self.disable_rules);
+            overridden.rules = overridden
+                .flatten_rules()
+                .into_iter()
+                .filter(|rule| !disabled.contains(rule))
+                .collect();
+        }
+
+        Ok(overridden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn deserialize() -> Result<()> {
+        let text = indoc! {r#"
+            files = ["CHANGELOG.md"]
+            disable-rules = ["MD013"]
+
+            [md024]
+            allow-different-nesting = true
+        "#};
+        let actual: Override = toml::from_str(text).into_diagnostic()?;
+        let mut md024 = Table::new();
+        md024.insert("allow-different-nesting".to_owned(), Value::Boolean(true));
+        let mut settings = Table::new();
+        settings.insert("md024".to_owned(), Value::Table(md024));
+        let expected = Override {
+            files: vec![Glob::new("CHANGELOG.md").into_diagnostic()?],
+            rules: None,
+            extend_rules: vec![],
+            disable_rules: vec![RuleSet::MD013],
+            settings,
+        };
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn apply_settings() -> Result<()> {
+        let text = indoc! {"
+            files = ['*.md']
+
+            [md013]
+            line-length = 120
+        "};
+        let overrides: Override = toml::from_str(text).into_diagnostic()?;
+        let mut lint = Lint::default();
+        lint.md013.tables = false;
+        let actual = overrides.apply(&lint)?;
+        let mut expected = lint;
+        expected.md013.line_length = 120;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn apply_rules() -> Result<()> {
+        let overrides = Override {
+            files: vec![],
+            rules: Some(vec![RuleSet::MD001, RuleSet::LineLength]),
+            extend_rules: vec![RuleSet::Html],
+            disable_rules: vec![RuleSet::MD013],
+            settings: Table::new(),
+        };
+        let actual = overrides.apply(&Lint::default())?;
+        assert_eq!(actual.rules, vec![RuleSet::MD001, RuleSet::MD033]);
+        Ok(())
+    }
+
+    #[test]
+    fn apply_unknown_setting() -> Result<()> {
+        let text = indoc! {"
+            files = ['*.md']
+            quiet = true
+        "};
+        let overrides: Override = toml::from_str(text).into_diagnostic()?;
+        let actual = overrides
+            .apply(&Lint::default())
+            .map_err(|err| err.to_string());
+        assert_eq!(
+            actual,
+            Err("`quiet` cannot be overridden per file".to_owned())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,717 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use miette::{IntoDiagnostic as _, Result, miette};
+use regex::Regex;
+use serde_json::{Map, Value as JsonValue};
+use toml::{Table, Value};
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::Config;
+use super::lint::{Lint, RuleSet};
+
+// Default rule settings, used to check whether an option is supported
+static DEFAULT_SETTINGS: LazyLock<Table> =
+    LazyLock::new(|| Table::try_from(Lint::default()).unwrap_or_default());
+
+/// Configuration translated from markdownlint, markdownlint-cli2 or mdl
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    rules: BTreeSet<RuleSet>,
+    severities: Table,
+    settings: Table,
+    options: Table,
+    warnings: Vec<String>,
+}
+
+impl Migration {
+    /// Configuration files searched in the current directory, in order of precedence
+    pub const FILE_NAMES: [&str; 9] = [
+        ".markdownlint-cli2.jsonc",
+        ".markdownlint-cli2.yaml",
+        ".markdownlint.jsonc",
+        ".markdownlint.json",
+        ".markdownlint.yaml",
+        ".markdownlint.yml",
+        ".markdownlintrc",
+        ".mdlrc",
+        ".mdl_style.rb",
+    ];
+
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rules: Lint::default().flatten_rules().into_iter().collect(),
+            severities: Table::new(),
+            settings: Table::new(),
+            options: Table::new(),
+            warnings: vec![],
+        }
+    }
+
+    /// Returns the first configuration file found in `dir`, if any
+    #[inline]
+    pub fn find(dir: &Path) -> Result<Option<PathBuf>> {
+        for file_name in Self::FILE_NAMES {
+            let path = dir.join(file_name);
+            if fs::exists(&path).into_diagnostic()? {
+                return Ok(Some(path));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .into_diagnostic()
+            .map_err(|err| err.wrap_err(format!("Failed to read {}", path.display())))?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let mut migration = Self::new();
+        match (file_name, extension) {
+            (".mdlrc", _) => {
+                let dir = path.parent().unwrap_or_else(|| Path::new("."));
+                migration.apply_mdlrc(&text, dir)?;
+            }
+            (_, Some("rb")) => {
+                migration.rules.clear();
+                migration.apply_mdl_style(&text);
+            }
+            _ => {
+                let value = if matches!(extension, Some("yaml" | "yml")) {
+                    parse_yaml(&text)?
+                } else {
+                    parse_jsonc(&text)?
+                };
+                if file_name.contains("markdownlint-cli2") {
+                    migration.apply_markdownlint_cli2(&value);
+                } else {
+                    migration.apply_markdownlint(&value);
+                }
+            }
+        }
+
+        Ok(migration)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Returns the translated configuration as a `mado.toml` table
+    #[inline]
+    #[must_use]
+    pub fn to_table(&self) -> Table {
+        let mut lint = self.options.clone();
+        let default_rules: BTreeSet<_> = Lint::default().flatten_rules().into_iter().collect();
+        if self.rules != default_rules {
+            let rules = self
+                .rules
+                .iter()
+                .filter_map(|rule| Value::try_from(rule).ok())
+                .collect();
+            lint.insert("rules".to_owned(), Value::Array(rules));
+        }
+        if !self.severities.is_empty() {
+            lint.insert(
+                "severities".to_owned(),
+                Value::Table(self.severities.clone()),
+            );
+        }
+        lint.extend(self.settings.clone());
+
+        let mut table = Table::new();
+        table.insert("lint".to_owned(), Value::Table(lint));
+        table
+    }
+
+    #[inline]
+    pub fn to_config(&self) -> Result<Config> {
+        self.to_table().try_into().map_err(|err| miette!(err))
+    }
+
+    /// Applies a configuration object of markdownlint
+    #[inline]
+    pub fn apply_markdownlint(&mut self, value: &JsonValue) {
+        let Some(object) = value.as_object() else {
+            self.warn("configuration must be an object".to_owned());
+            return;
+        };
+
+        if object.get("default").and_then(JsonValue::as_bool) == Some(false) {
+            self.rules.clear();
+        }
+
+        for (key, setting) in object {
+            match key.as_str() {
+                "default" | "$schema" => {}
+                "extends" => self.warn(format!(
+                    "`extends` is not supported, migrate {setting} separately"
+                )),
+                _ => self.apply_rule(key, setting),
+            }
+        }
+    }
+
+    /// Applies a configuration object of markdownlint-cli2
+    #[inline]
+    pub fn apply_markdownlint_cli2(&mut self, value: &JsonValue) {
+        let Some(object) = value.as_object() else {
+            self.warn("configuration must be an object".to_owned());
+            return;
+        };
+
+        for (key, setting) in object {
+            match (key.as_str(), setting) {
+                ("config", config) => self.apply_markdownlint(config),
+                ("ignores", JsonValue::Array(ignores)) => {
+                    let exclude = ignores
+                        .iter()
+                        .filter_map(|ignore| ignore.as_str())
+                        .map(|ignore| Value::String(ignore.to_owned()))
+                        .collect();
+                    self.options
+                        .insert("exclude".to_owned(), Value::Array(exclude));
+                }
+                ("fix", JsonValue::Bool(fix)) => {
+                    self.options.insert("fix".to_owned(), Value::Boolean(*fix));
+                }
+                ("gitignore", JsonValue::Bool(gitignore)) => {
+                    self.options
+                        .insert("respect-gitignore".to_owned(), Value::Boolean(*gitignore));
+                }
+                ("$schema", _) => {}
+                _ => self.warn(format!("`{key}` is not supported")),
+            }
+        }
+    }
+
+    /// Applies a `.mdlrc` file, including the style file it refers to
+    #[inline]
+    pub fn apply_mdlrc(&mut self, text: &str, dir: &Path) -> Result<()> {
+        static LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+            #[allow(clippy::unwrap_used)]
+            Regex::new(r#"^:?(\w+)\s+['"]?(.*?)['"]?\s*$"#).unwrap()
+        });
+
+        let mut rules = None;
+        let mut tags = None;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some(captures) = LINE_RE.captures(line) else {
+                self.warn(format!("`{line}` is not supported"));
+                continue;
+            };
+
+            let (_, [key, value]) = captures.extract();
+            match key {
+                "style" => {
+                    let style = value.trim_start_matches("#{File.dirname(__FILE__)}/");
+                    let path = dir.join(style);
+                    let style_text =
+                        fs::read_to_string(&path).into_diagnostic().map_err(|err| {
+                            err.wrap_err(format!("Failed to read {}", path.display()))
+                        })?;
+                    self.rules.clear();
+                    self.apply_mdl_style(&style_text);
+                }
+                "rules" => rules = Some(value.to_owned()),
+                "tags" => tags = Some(value.to_owned()),
+                _ => self.warn(format!("`{key}` is not supported")),
+            }
+        }
+
+        // Rules and tags are filtered after the style is applied as mdl does
+        for filter in [tags, rules].into_iter().flatten() {
+            let (excluded, included): (Vec<_>, Vec<_>) = filter
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .partition(|name| name.starts_with('~'));
+            if !included.is_empty() {
+                let included_rules: BTreeSet<_> = included
+                    .iter()
+                    .filter_map(|name| self.rule_sets(name))
+                    .flatten()
+                    .collect();
+                self.rules.retain(|rule| included_rules.contains(rule));
+            }
+
+            for name in excluded {
+                if let Some(excluded_rules) = self.rule_sets(name.trim_start_matches('~')) {
+                    for rule in excluded_rules {
+                        self.rules.remove(&rule);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a style file of mdl
+    #[inline]
+    pub fn apply_mdl_style(&mut self, text: &str) {
+        static STATEMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+            #[allow(clippy::unwrap_used)]
+            Regex::new(r#"^(all|rule|exclude_rule|tag|exclude_tag)\b\s*\(?\s*(?:['":](\w+)['"]?)?(.*?)\)?\s*$"#)
+                .unwrap()
+        });
+        static PARAM_RE: LazyLock<Regex> = LazyLock::new(|| {
+            #[allow(clippy::unwrap_used)]
+            Regex::new(r#":?(\w+)(?:\s*=>\s*|:\s+)('[^']*'|"[^"]*"|\[[^\]]*\]|[^,\s]+)"#).unwrap()
+        });
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some(captures) = STATEMENT_RE.captures(line) else {
+                self.warn(format!("`{line}` is not supported"));
+                continue;
+            };
+
+            let statement = captures.get(1).map_or("", |m| m.as_str());
+            let name = captures.get(2).map_or("", |m| m.as_str());
+            let params = captures.get(3).map_or("", |m| m.as_str());
+            match statement {
+                "all" => self.rules = Lint::default().flatten_rules().into_iter().collect(),
+                "rule" | "tag" => {
+                    let mut options = Map::new();
+                    for param in PARAM_RE.captures_iter(params) {
+                        let (_, [key, value]) = param.extract();
+                        options.insert(key.to_owned(), ruby_value(value));
+                    }
+                    if options.is_empty() {
+                        self.apply_rule(name, &JsonValue::Bool(true));
+                    } else {
+                        self.apply_rule(name, &JsonValue::Object(options));
+                    }
+                }
+                _ => self.apply_rule(name, &JsonValue::Bool(false)),
+            }
+        }
+    }
+
+    // Applies a value for a rule, alias or tag
+    fn apply_rule(&mut self, name: &str, value: &JsonValue) {
+        let Some(rules) = self.rule_sets(name) else {
+            return;
+        };
+
+        let (enabled, severity) = match value {
+            JsonValue::Bool(enabled) => (*enabled, None),
+            JsonValue::String(severity) if severity == "error" || severity == "warning" => {
+                (true, Some(severity))
+            }
+            JsonValue::Object(_) => (true, None),
+            _ => {
+                self.warn(format!("unsupported value for `{name}`: {value}"));
+                return;
+            }
+        };
+
+        for rule in &rules {
+            if enabled {
+                self.rules.insert(rule.clone());
+            } else {
+                self.rules.remove(rule);
+            }
+
+            if let Some(severity) = severity
+                && let Ok(Value::String(rule_name)) = Value::try_from(rule)
+            {
+                self.severities
+                    .insert(rule_name, Value::String(severity.clone()));
+            }
+        }
+
+        match (value, rules.as_slice()) {
+            (JsonValue::Object(options), [rule]) => {
+                for (option, option_value) in options {
+                    self.apply_option(rule, option, option_value);
+                }
+            }
+            // Options of a tag cannot be told apart for each of its rules
+            (JsonValue::Object(options), _) => {
+                for (option, option_value) in options {
+                    self.warn(format!(
+                        "option `{option}` of `{name}` is not supported for multiple rules: {option_value}"
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_option(&mut self, rule: &RuleSet, option: &str, value: &JsonValue) {
+        let rule_name = Value::try_from(rule)
+            .ok()
+            .and_then(|name| name.as_str().map(str::to_owned))
+            .unwrap_or_default();
+        let (key, toml_value) = match (rule, option, value) {
+            // NOTE: `siblings_only` is the current name of `allow_different_nesting` in markdownlint
+            (RuleSet::MD024, "siblings_only", _) => {
+                ("allow-different-nesting".to_owned(), to_toml(value))
+            }
+            (RuleSet::MD013, "ignore_code_blocks", JsonValue::Bool(ignore)) => {
+                ("code-blocks".to_owned(), Some(Value::Boolean(!ignore)))
+            }
+            _ => (option.replace('_', "-"), to_toml(value)),
+        };
+
+        let candidates = match toml_value {
+            Some(Value::String(string)) if string.contains('_') => vec![
+                Value::String(string.clone()),
+                Value::String(string.replace('_', "-")),
+            ],
+            Some(other) => vec![other],
+            None => vec![],
+        };
+
+        let table_name = rule_name.to_lowercase();
+        for candidate in candidates {
+            let mut settings = Table::new();
+            settings.insert(key.clone(), candidate.clone());
+            let mut lint = Table::new();
+            lint.insert(table_name.clone(), Value::Table(settings.clone()));
+            let is_known = DEFAULT_SETTINGS.get(&table_name).is_some_and(|table| {
+                table
+                    .as_table()
+                    .is_some_and(|defaults| defaults.contains_key(&key))
+            });
+            if is_known && Value::Table(lint).try_into::<Lint>().is_ok() {
+                if let Value::Table(rule_settings) = self
+                    .settings
+                    .entry(table_name)
+                    .or_insert_with(|| Value::Table(Table::new()))
+                {
+                    rule_settings.extend(settings);
+                }
+                return;
+            }
+        }
+
+        self.warn(format!(
+            "option `{option}` of {rule_name} is not supported: {value}"
+        ));
+    }
+
+    // Returns rules for a rule name, alias or tag
+    fn rule_sets(&mut self, name: &str) -> Option<Vec<RuleSet>> {
+        // NOTE: markdownlint uses "heading" where mdl uses "header" in aliases and tags
+        let normalized = name
+            .to_lowercase()
+            .replace('_', "-")
+            .replace("heading", "header");
+        let rule_name = RuleSet::rule_name(&normalized).map_or_else(
+            || tag_name(&normalized).to_owned(),
+            |rule_name| (*rule_name).to_owned(),
+        );
+        if let Ok(rule) = Value::String(rule_name).try_into::<RuleSet>() {
+            return Some(Lint::flatten(&[rule]));
+        }
+
+        self.warn(format!("rule `{name}` is not supported"));
+        None
+    }
+
+    fn warn(&mut self, message: String) {
+        self.warnings.push(message);
+    }
+}
+
+impl Default for Migration {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Maps aliases and tags only markdownlint has to names of mado
+fn tag_name(tag: &str) -> &str {
+    match tag {
+        "first-line-header" => "MD041",
+        "single-title" => "MD025",
+        "hard-tabs" => "hard-tab",
+        _ => tag,
+    }
+}
+
+fn to_toml(value: &JsonValue) -> Option<Value> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Bool(bool) => Some(Value::Boolean(*bool)),
+        JsonValue::Number(number) => number
+            .as_i64()
+            .map(Value::Integer)
+            .or_else(|| number.as_f64().map(Value::Float)),
+        JsonValue::String(string) => Some(Value::String(string.clone())),
+        JsonValue::Array(values) => Some(Value::Array(values.iter().filter_map(to_toml).collect())),
+        JsonValue::Object(object) => Some(Value::Table(
+            object
+                .iter()
+                .filter_map(|(key, item)| to_toml(item).map(|converted| (key.clone(), converted)))
+                .collect(),
+        )),
+    }
+}
+
+// Parses a literal of Ruby used in style files of mdl
+fn ruby_value(literal: &str) -> JsonValue {
+    if let Ok(integer) = literal.parse::<i64>() {
+        return JsonValue::from(integer);
+    }
+
+    match literal {
+        "true" => JsonValue::Bool(true),
+        "false" => JsonValue::Bool(false),
+        _ if literal.starts_with('[') => JsonValue::Array(
+            literal
+                .trim_matches(['[', ']'])
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(ruby_value)
+                .collect(),
+        ),
+        _ => JsonValue::String(
+            literal
+                .trim_start_matches(':')
+                .trim_matches(['\'', '"'])
+                .to_owned(),
+        ),
+    }
+}
+
+/// Parses JSON with comments and trailing commas
+pub(crate) fn parse_jsonc(text: &str) -> Result<JsonValue> {
+    let mut json = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => {
+                json.push(char);
+                while let Some(string_char) = chars.next() {
+                    json.push(string_char);
+                    match string_char {
+                        '\\' => json.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|next| *next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for comment_char in chars.by_ref() {
+                    if prev == '*' && comment_char == '/' {
+                        break;
+                    }
+                    prev = comment_char;
+                }
+            }
+            ']' | '}' => {
+                // Remove a trailing comma
+                let trimmed_len = json.trim_end().len();
+                if json[..trimmed_len].ends_with(',') {
+                    json.truncate(trimmed_len - 1);
+                }
+                json.push(char);
+            }
+            _ => json.push(char),
+        }
+    }
+
+    serde_json::from_str(&json).into_diagnostic()
+}
+
+pub(crate) fn parse_yaml(text: &str) -> Result<JsonValue> {
+    let docs = YamlLoader::load_from_str(text).into_diagnostic()?;
+    Ok(docs.first().map_or(JsonValue::Null, yaml_to_json))
+}
+
+fn yaml_to_json(yaml: &Yaml) -> JsonValue {
+    match yaml {
+        Yaml::Real(real) => real.parse::<f64>().map_or(JsonValue::Null, JsonValue::from),
+        Yaml::Integer(integer) => JsonValue::from(*integer),
+        Yaml::String(string) => JsonValue::String(string.clone()),
+        Yaml::Boolean(bool) => JsonValue::Bool(*bool),
+        Yaml::Array(values) => JsonValue::Array(values.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => JsonValue::Object(
+            hash.iter()
+                .filter_map(|(key, value)| match key {
+                    Yaml::String(key) => Some((key.clone(), yaml_to_json(value))),
+                    Yaml::Integer(key) => Some((key.to_string(), yaml_to_json(value))),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => JsonValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::rule::Severity;
+    use crate::rule::md003::HeadingStyle;
+    use crate::rule::md029::OrderedListStyle;
+
+    #[test]
+    fn apply_markdownlint() -> Result<()> {
+        let value = parse_jsonc(indoc! {r#"
+            {
+              // Comments and trailing commas are allowed
+              "default": false,
+              "line-length": { "line_length": 100, "strict": true },
+              "headings": { "style": "atx" },
+              "MD003": { "style": "setext_with_atx" }, /* block comment */
+              "MD024": { "siblings_only": true },
+              "MD041": false,
+              "MD042": true,
+              "no-trailing-punctuation": "warning",
+            }
+        "#})?;
+        let mut migration = Migration::new();
+        migration.apply_markdownlint(&value);
+
+        let actual = migration.to_config()?;
+        let mut expected = Config::default();
+        expected.lint.rules = vec![
+            RuleSet::MD001,
+            RuleSet::MD002,
+            RuleSet::MD003,
+            RuleSet::MD013,
+            RuleSet::MD018,
+            RuleSet::MD019,
+            RuleSet::MD020,
+            RuleSet::MD021,
+            RuleSet::MD022,
+            RuleSet::MD023,
+            RuleSet::MD024,
+            RuleSet::MD025,
+            RuleSet::MD026,
+            RuleSet::MD036,
+        ];
+        expected
+            .lint
+            .severities
+            .insert(RuleSet::MD026, Severity::Warning);
+        expected.lint.md003.style = HeadingStyle::SetextWithAtx;
+        expected.lint.md013.line_length = 100;
+        expected.lint.md024.allow_different_nesting = true;
+        assert_eq!(actual, expected);
+        assert_eq!(
+            migration.warnings(),
+            [
+                "option `strict` of MD013 is not supported: true",
+                "option `style` of `headings` is not supported for multiple rules: \"atx\"",
+                "rule `MD042` is not supported",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn apply_markdownlint_cli2() -> Result<()> {
+        let value = parse_yaml(indoc! {"
+            config:
+              no-hard-tabs: false
+            ignores:
+              - CHANGELOG.md
+            gitignore: false
+            customRules: []
+        "})?;
+        let mut migration = Migration::new();
+        migration.apply_markdownlint_cli2(&value);
+
+        let actual = migration.to_config()?;
+        let mut expected = Config::default();
+        expected.lint.rules.retain(|rule| *rule != RuleSet::MD010);
+        expected.lint.exclude = vec![globset::Glob::new("CHANGELOG.md").into_diagnostic()?];
+        expected.lint.respect_gitignore = false;
+        assert_eq!(actual, expected);
+        assert_eq!(migration.warnings(), ["`customRules` is not supported"]);
+        Ok(())
+    }
+
+    #[test]
+    fn apply_mdl_style() -> Result<()> {
+        let mut migration = Migration::new();
+        migration.rules.clear();
+        migration.apply_mdl_style(indoc! {"
+            all
+            exclude_tag :line_length
+            rule 'MD007', :indent => 2
+            rule 'MD029', style: :ordered
+            exclude_rule 'MD041'
+        "});
+
+        let actual = migration.to_config()?;
+        let mut expected = Config::default();
+        expected
+            .lint
+            .rules
+            .retain(|rule| *rule != RuleSet::MD013 && *rule != RuleSet::MD041);
+        expected.lint.md007.indent = 2;
+        expected.lint.md029.style = OrderedListStyle::Ordered;
+        assert_eq!(actual, expected);
+        assert!(migration.warnings().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn apply_mdlrc() -> Result<()> {
+        let mut migration = Migration::new();
+        migration.apply_mdlrc(
+            "rules \"MD001,MD013,~MD013\"\nverbose true\n",
+            Path::new("."),
+        )?;
+
+        let actual = migration.to_config()?;
+        let mut expected = Config::default();
+        expected.lint.rules = vec![RuleSet::MD001];
+        assert_eq!(actual, expected);
+        assert_eq!(migration.warnings(), ["`verbose` is not supported"]);
+        Ok(())
+    }
+
+    #[test]
+    fn to_table_default() {
+        let migration = Migration::new();
+        let mut expected = Table::new();
+        expected.insert("lint".to_owned(), Value::Table(Table::new()));
+        assert_eq!(migration.to_table(), expected);
+    }
+
+    #[test]
+    fn parse_jsonc_with_comment_like_strings() -> Result<()> {
+        let actual = parse_jsonc(r#"{ "url": "https://example.com/*", "list": [1, 2,], }"#)?;
+        let expected = serde_json::json!({ "url": "https://example.com/*", "list": [1, 2] });
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ pub struct Document<'a> {
     pub ast: &'a AstNode<'a>,
     pub text: String,
     pub lines: Vec<String>,
+    line_offsets: Vec<usize>,
 }
 
 impl<'a> Document<'a> {
@@ -23,12 +24,21 @@ impl<'a> Document<'a> {
         options.extension.table = true;
         let ast = parse_document(arena, &text, &options);
         let lines: Vec<_> = text.lines().map(ToOwned::to_owned).collect();
+        let line_offsets = text
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some(start)
+            })
+            .collect();
 
         Ok(Self {
             path,
             ast,
             text,
             lines,
+            line_offsets,
         })
     }
 
@@ -38,6 +48,23 @@ impl<'a> Document<'a> {
         Self::new(arena, path.to_path_buf(), text)
     }
 
+    /// Returns the byte offset of the given 1-based line and column
+    #[inline]
+    #[must_use]
+    pub fn offset(&self, lineno: usize, column: usize) -> Option<usize> {
+        let line_offset = self.line_offsets.get(lineno.checked_sub(1)?)?;
+        Some(line_offset + column.checked_sub(1)?)
+    }
+
+    /// Returns the 1-based line containing the given byte offset
+    #[inline]
+    #[must_use]
+    pub fn lineno(&self, offset: usize) -> usize {
+        self.line_offsets
+            .partition_point(|line_offset| *line_offset <= offset)
+            .max(1)
+    }
+
     #[inline]
     #[must_use]
     pub fn front_matter(&self) -> Option<String> {
@@ -65,6 +92,35 @@ mod tests {
         assert!(Document::open(&arena, path).is_ok());
     }
 
+    #[test]
+    fn offset() -> Result<()> {
+        let text = "foo\r\nbar\n\nbaz".to_owned();
+        let arena = Arena::new();
+        let path = Path::new("test.md").to_path_buf();
+        let doc = Document::new(&arena, path, text)?;
+        assert_eq!(doc.offset(1, 1), Some(0));
+        assert_eq!(doc.offset(2, 2), Some(6));
+        assert_eq!(doc.offset(3, 1), Some(9));
+        assert_eq!(doc.offset(4, 3), Some(12));
+        assert_eq!(doc.offset(5, 1), None);
+        assert_eq!(doc.offset(0, 1), None);
+        Ok(())
+    }
+
+    #[test]
+    fn lineno() -> Result<()> {
+        let text = "foo\r\nbar\n\nbaz".to_owned();
+        let arena = Arena::new();
+        let path = Path::new("test.md").to_path_buf();
+        let doc = Document::new(&arena, path, text)?;
+        assert_eq!(doc.lineno(0), 1);
+        assert_eq!(doc.lineno(4), 1);
+        assert_eq!(doc.lineno(5), 2);
+        assert_eq!(doc.lineno(9), 3);
+        assert_eq!(doc.lineno(13), 4);
+        Ok(())
+    }
+
     #[test]
     fn front_matter_some() -> Result<()> {
         let front_matter = indoc! {"
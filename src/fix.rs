@@ -0,0 +1,127 @@
+use core::cmp::Ordering;
+use core::ops::Range;
+
+/// A replacement of a byte range in a document text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edit {
+    range: Range<usize>,
+    content: String,
+}
+
+impl Edit {
+    #[inline]
+    #[must_use]
+    pub const fn new(range: Range<usize>, content: String) -> Self {
+        Self { range, content }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn deletion(range: Range<usize>) -> Self {
+        Self::new(range, String::new())
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn insertion(offset: usize, content: String) -> Self {
+        Self::new(offset..offset, content)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+impl PartialOrd for Edit {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Edit {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.range
+            .start
+            .cmp(&other.range.start)
+            .then_with(|| self.range.end.cmp(&other.range.end))
+            .then_with(|| self.content.cmp(&other.content))
+    }
+}
+
+/// Applies non-overlapping edits to `text`.
+///
+/// Edits are applied in order of their ranges. An edit overlapping with an
+/// already accepted edit is skipped, so that it can be recomputed against the
+/// updated text on the next pass. Returns `None` if no edit was applied.
+#[inline]
+#[must_use]
+pub fn apply(text: &str, mut edits: Vec<Edit>) -> Option<String> {
+    edits.sort();
+    edits.dedup();
+
+    let mut fixed = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut applied = false;
+    for edit in edits {
+        let Range { start, end } = edit.range;
+        if start < cursor || end > text.len() || start > end {
+            continue;
+        }
+
+        fixed.push_str(text.get(cursor..start)?);
+        fixed.push_str(&edit.content);
+        cursor = end;
+        applied = true;
+    }
+
+    if !applied {
+        return None;
+    }
+
+    fixed.push_str(text.get(cursor..)?);
+    Some(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn apply_empty() {
+        assert_eq!(apply("text", vec![]), None);
+    }
+
+    #[test]
+    fn apply_edits() {
+        let edits = vec![
+            Edit::insertion(4, "\n".to_owned()),
+            Edit::deletion(1..2),
+            Edit::new(2..3, "X".to_owned()),
+        ];
+        assert_eq!(apply("text", edits), Some("tXt\n".to_owned()));
+    }
+
+    #[test]
+    fn apply_overlapping_edits() {
+        let edits = vec![Edit::deletion(0..3), Edit::new(2..4, "X".to_owned())];
+        assert_eq!(apply("text", edits), Some("t".to_owned()));
+    }
+
+    #[test]
+    fn apply_out_of_range_edits() {
+        let edits = vec![Edit::deletion(3..10)];
+        assert_eq!(apply("text", edits), None);
+    }
+}
@@ -6,6 +6,7 @@ pub mod collection;
 pub mod command;
 pub mod config;
 mod document;
+pub mod fix;
 mod output;
 pub mod rule;
 pub mod service;
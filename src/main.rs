@@ -15,6 +15,7 @@ static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+use std::path::Path;
 use std::process::ExitCode;
 
 use clap::CommandFactory as _;
@@ -24,8 +25,16 @@ use miette::Result;
 
 use mado::Cli;
 use mado::Command;
+use mado::command::cache::CacheCleaner;
 use mado::command::check::Checker;
+use mado::command::check::watch::FileWatcher;
+use mado::command::config::ConfigMigrator;
+use mado::command::fmt::{self, FileFormatter};
 use mado::command::generate_shell_completion::ShellCompletionGenerator;
+use mado::command::server::{self, LanguageServer};
+use mado::command::{CacheCommand, ConfigCommand};
+use mado::service::cache::Cache;
+use mado::service::changes::Changes;
 
 fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
@@ -34,18 +43,97 @@ fn main() -> Result<ExitCode> {
         Command::Check {
             files,
             output_format,
+            output_file,
             quiet,
+            max_warnings,
+            fix,
+            report_unused_suppressions,
+            diff,
+            no_cache,
+            baseline,
+            write_baseline,
             exclude,
+            watch,
+            changed_since,
+            whole_file,
         } => {
+            // Discover the nearest configuration for each file unless it is given explicitly
+            let discover = cli.config.is_none();
             let options = Options {
                 output_format: output_format.clone(),
+                output_files: output_file.clone(),
                 config_path: cli.config,
                 quiet: *quiet,
+                max_warnings: *max_warnings,
+                fix: *fix,
+                report_unused_suppressions: *report_unused_suppressions,
+                no_cache: *no_cache,
+                baseline: baseline.clone(),
                 exclude: exclude.clone(),
             };
+            if *watch {
+                let watcher = FileWatcher::new(files, options, discover)?;
+                return watcher.watch();
+            }
+
+            let (config, warnings) = options.to_config()?;
+            let checker = match changed_since {
+                Some(rev) => {
+                    let changes = Changes::since(Path::new("."), rev)?;
+                    let changed_files = changes.files(files)?;
+                    let checker = Checker::new(&changed_files, config, discover, *diff)?;
+                    if *whole_file {
+                        checker
+                    } else {
+                        checker.with_changes(changes)
+                    }
+                }
+                None => Checker::new(files, config, discover, *diff)?,
+            };
+            let checker = checker
+                .with_output_formats(output_format.clone())
+                .with_warnings(warnings);
+            match write_baseline {
+                Some(path) => checker.write_baseline(path),
+                None => checker.check(),
+            }
+        }
+        Command::Fmt {
+            files,
+            check,
+            exclude,
+        } => {
+            // Discover the nearest configuration for each file unless it is given explicitly
+            let discover = cli.config.is_none();
+            let options = fmt::Options {
+                config_path: cli.config,
+                exclude: exclude.clone(),
+            };
+            let (config, warnings) = options.to_config()?;
+            let formatter = FileFormatter::new(files, config, discover, *check);
+            formatter.with_warnings(warnings).format()
+        }
+        Command::Config {
+            command: ConfigCommand::Migrate { file },
+        } => {
+            let migrator = ConfigMigrator::new(file.clone());
+            migrator.migrate()
+        }
+        Command::Cache {
+            command: CacheCommand::Clean,
+        } => {
+            let cleaner = CacheCleaner::new(Cache::find_dir(Path::new("."))?);
+            cleaner.clean()
+        }
+        Command::Server => {
+            // Discover the nearest configuration for each document unless it is given explicitly
+            let discover = cli.config.is_none();
+            let options = server::Options {
+                config_path: cli.config,
+            };
             let config = options.to_config()?;
-            let checker = Checker::new(files, config)?;
-            checker.check()
+            let server = LanguageServer::new(config, discover)?;
+            server.serve()
         }
         Command::GenerateShellCompletion { shell } => {
             let cmd = Cli::command();
@@ -1,21 +1,53 @@
 use core::cmp::Ordering;
+use std::env;
 
 use clap::ValueEnum;
+use colored::{ColoredString, Colorize as _};
 use serde::{Deserialize, Serialize};
 
+mod checkstyle;
 mod concise;
+mod diff;
+mod full;
+mod github;
+mod gitlab;
+mod html;
+mod json;
+mod junit;
 mod markdownlint;
 mod mdl;
+mod sarif;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum Format {
     Concise,
     Mdl,
     Markdownlint,
+    Json,
+    #[serde(rename = "json-lines")]
+    JsonLines,
+    Sarif,
+    Junit,
+    Checkstyle,
+    Github,
+    Gitlab,
+    Full,
+    Html,
 }
 
 impl Format {
+    /// Returns `github` on GitHub Actions and `concise` otherwise
+    #[inline]
+    #[must_use]
+    pub fn from_env() -> Self {
+        if env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true") {
+            Self::Github
+        } else {
+            Self::Concise
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn sorter(&self) -> fn(a: &Violation, b: &Violation) -> Ordering {
@@ -23,15 +55,108 @@ impl Format {
             Self::Concise => |a, b| Concise::new(a).cmp(&Concise::new(b)),
             Self::Mdl => |a, b| Mdl::new(a).cmp(&Mdl::new(b)),
             Self::Markdownlint => |a, b| Markdownlint::new(a).cmp(&Markdownlint::new(b)),
+            Self::Github => |a, b| Github::new(a).cmp(&Github::new(b)),
+            Self::Full
+            | Self::Json
+            | Self::JsonLines
+            | Self::Sarif
+            | Self::Junit
+            | Self::Checkstyle
+            | Self::Gitlab
+            | Self::Html => Violation::cmp,
         }
     }
 }
 
+pub use checkstyle::Checkstyle;
 pub use concise::Concise;
+pub use diff::Diff;
+pub use full::Full;
+pub use github::Github;
+pub use gitlab::Gitlab;
+pub use html::Html;
+pub use json::{Json, JsonLines};
+pub use junit::Junit;
 pub use markdownlint::Markdownlint;
 pub use mdl::Mdl;
+pub use sarif::Sarif;
 
 use crate::Violation;
+use crate::rule::Severity;
+
+// Documentation of rules, which has an anchor for each rule (e.g. `#md013`)
+pub const RULES_URI: &str = "https://github.com/markdownlint/markdownlint/blob/main/docs/RULES.md";
+
+/// Describes the number of violations by severity (e.g. "1 error and 2 warnings")
+#[inline]
+#[must_use]
+pub fn summarize(violations: &[Violation]) -> String {
+    let count = |severity| {
+        violations
+            .iter()
+            .filter(|violation| violation.severity() == severity)
+            .count()
+    };
+    let mut counts = vec![];
+    for (num, severity) in [
+        (count(Severity::Error), Severity::Error),
+        (count(Severity::Warning), Severity::Warning),
+        (count(Severity::Info), Severity::Info),
+    ] {
+        // "info" has no plural, so info violations are counted as messages
+        let noun = match severity {
+            Severity::Info => format!("{severity} message"),
+            Severity::Error | Severity::Warning => severity.to_string(),
+        };
+        if num == 1 {
+            counts.push(format!("1 {noun}"));
+        } else if num > 1 {
+            counts.push(format!("{num} {noun}s"));
+        }
+    }
+
+    match counts.split_last() {
+        None => "0 errors".to_owned(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+// Colors `text` by severity
+fn colorize(text: &str, severity: Severity) -> ColoredString {
+    match severity {
+        Severity::Error => text.red(),
+        Severity::Warning => text.yellow(),
+        Severity::Info => text.blue(),
+    }
+}
+
+// Labels violations other than errors, which are shown as they always have been
+fn severity_label(severity: Severity) -> String {
+    match severity {
+        Severity::Error => String::new(),
+        Severity::Warning | Severity::Info => format!("[{severity}] "),
+    }
+}
+
+// Colors the label of severities, which is empty for errors
+fn colored_severity_label(severity: Severity) -> String {
+    match severity {
+        Severity::Error => String::new(),
+        Severity::Warning | Severity::Info => {
+            colorize(&severity_label(severity), severity).to_string()
+        }
+    }
+}
+
+// Escapes special characters in XML (and HTML) attributes and text
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
 
 #[cfg(test)]
 mod tests {
@@ -82,6 +207,27 @@ mod tests {
         ]
     }
 
+    #[test]
+    fn summarize() {
+        let violations = violations();
+        assert_eq!(super::summarize(&[]), "0 errors");
+        assert_eq!(super::summarize(&violations[..1]), "1 error");
+        assert_eq!(super::summarize(&violations), "12 errors");
+
+        let mut violations = violations;
+        violations[0] = violations[0].clone().with_severity(Severity::Warning);
+        violations[1] = violations[1].clone().with_severity(Severity::Info);
+        violations[2] = violations[2].clone().with_severity(Severity::Info);
+        assert_eq!(
+            super::summarize(&violations[..2]),
+            "1 warning and 1 info message"
+        );
+        assert_eq!(
+            super::summarize(&violations),
+            "9 errors, 1 warning and 2 info messages"
+        );
+    }
+
     #[test]
     fn sorter_concise() {
         let violations = violations();
@@ -104,6 +250,17 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn sorter_github() {
+        let violations = violations();
+        let mut actual = violations.clone();
+        actual.sort_by(Format::Github.sorter());
+        let mut outputs: Vec<_> = violations.iter().map(Github::new).collect();
+        outputs.sort();
+        let expected: Vec<_> = outputs.iter().map(|o| o.violation().clone()).collect();
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn sorter_markdownlint() {
         let violations = violations();
@@ -0,0 +1,103 @@
+use core::fmt::{Display, Error, Formatter, Result};
+
+use super::escape_xml as escape;
+use crate::Violation;
+
+/// Violations as a Checkstyle XML report, grouped by file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkstyle<'a> {
+    violations: &'a [Violation],
+}
+
+impl<'a> Checkstyle<'a> {
+    /// `violations` must be sorted by path so that each file appears once
+    pub const fn new(violations: &'a [Violation]) -> Self {
+        Self { violations }
+    }
+}
+
+impl Display for Checkstyle<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(f, r#"<checkstyle version="4.3">"#)?;
+        for (index, violation) in self.violations.iter().enumerate() {
+            let is_new_file = index == 0 || self.violations[index - 1].path() != violation.path();
+            if is_new_file {
+                if index > 0 {
+                    writeln!(f, "  </file>")?;
+                }
+                let name = escape(violation.path().to_str().ok_or(Error)?);
+                writeln!(f, r#"  <file name="{name}">"#)?;
+            }
+
+            writeln!(
+                f,
+                r#"    <error line="{}" column="{}" severity="{}" message="{}" source="mado.{}"/>"#,
+                violation.position().start.line,
+                violation.position().start.column,
+                violation.severity(),
+                escape(violation.description()),
+                escape(violation.name())
+            )?;
+        }
+        if !self.violations.is_empty() {
+            writeln!(f, "  </file>")?;
+        }
+        write!(f, "</checkstyle>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comrak::nodes::Sourcepos;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "name",
+        description: "\"description\" & more",
+        aliases: &["alias"],
+        tags: &[Tag::Atx],
+    };
+
+    #[test]
+    fn display_fmt() {
+        let path1 = Path::new("<a>.md").to_path_buf();
+        let path2 = Path::new("b.md").to_path_buf();
+        let violations = [
+            Violation::new(path1.clone(), &METADATA, Sourcepos::from((1, 1, 1, 5))),
+            Violation::new(path1, &METADATA, Sourcepos::from((3, 2, 3, 5))),
+            Violation::new(path2, &METADATA, Sourcepos::from((2, 1, 2, 1))),
+        ];
+        let actual = Checkstyle::new(&violations).to_string();
+        let expected = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <checkstyle version="4.3">
+              <file name="&lt;a&gt;.md">
+                <error line="1" column="1" severity="error" message="&quot;description&quot; &amp; more" source="mado.name"/>
+                <error line="3" column="2" severity="error" message="&quot;description&quot; &amp; more" source="mado.name"/>
+              </file>
+              <file name="b.md">
+                <error line="2" column="1" severity="error" message="&quot;description&quot; &amp; more" source="mado.name"/>
+              </file>
+            </checkstyle>"#};
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn display_fmt_empty() {
+        let actual = Checkstyle::new(&[]).to_string();
+        let expected = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <checkstyle version="4.3">
+            </checkstyle>"#};
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,6 +3,7 @@ use core::fmt::{Display, Error, Formatter, Result};
 
 use colored::Colorize as _;
 
+use super::{colored_severity_label, colorize};
 use crate::Violation;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,14 +28,15 @@ impl Display for Concise<'_> {
         let path = self.violation.path().to_str().ok_or(Error)?;
         write!(
             f,
-            "{}{}{}{}{}{} {} {}",
+            "{}{}{}{}{}{} {} {}{}",
             path.bold(),
             ":".blue(),
             self.violation.position().start.line,
             ":".blue(),
             self.violation.position().start.column,
             ":".blue(),
-            self.violation.name().red().bold(),
+            colorize(self.violation.name(), self.violation.severity()).bold(),
+            colored_severity_label(self.violation.severity()),
             self.violation.description()
         )
     }
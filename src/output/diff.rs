@@ -0,0 +1,198 @@
+use core::fmt::{Display, Error, Formatter, Result};
+use core::ops::Range;
+use std::path::Path;
+
+use colored::Colorize as _;
+use diff::Result as Change;
+
+/// Unified diff between the original and the fixed text of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff<'a> {
+    path: &'a Path,
+    original: &'a str,
+    modified: &'a str,
+}
+
+impl<'a> Diff<'a> {
+    const CONTEXT: usize = 3;
+
+    pub const fn new(path: &'a Path, original: &'a str, modified: &'a str) -> Self {
+        Self {
+            path,
+            original,
+            modified,
+        }
+    }
+
+    // Groups changes into ranges of `changes`, merging ones whose contexts overlap
+    fn hunks<T>(changes: &[Change<T>]) -> Vec<Range<usize>> {
+        let mut hunks: Vec<Range<usize>> = vec![];
+        for (i, change) in changes.iter().enumerate() {
+            if matches!(change, Change::Both(..)) {
+                continue;
+            }
+
+            let start = i.saturating_sub(Self::CONTEXT);
+            let end = (i + Self::CONTEXT + 1).min(changes.len());
+            match hunks.last_mut() {
+                Some(hunk) if start <= hunk.end => hunk.end = end,
+                _ => hunks.push(start..end),
+            }
+        }
+
+        hunks
+    }
+
+    // Formats a line range as GNU diff does, where `start` is the number of preceding lines
+    fn range(start: usize, len: usize) -> String {
+        match len {
+            0 => format!("{start},0"),
+            1 => format!("{}", start + 1),
+            _ => format!("{},{len}", start + 1),
+        }
+    }
+
+    fn write_line(f: &mut Formatter<'_>, prefix: char, line: &str) -> Result {
+        let content = line.strip_suffix('\n').unwrap_or(line);
+        let line_str = format!("{prefix}{content}");
+        match prefix {
+            '-' => writeln!(f, "{}", line_str.red())?,
+            '+' => writeln!(f, "{}", line_str.green())?,
+            _ => writeln!(f, "{line_str}")?,
+        }
+
+        if !line.ends_with('\n') {
+            writeln!(f, "\\ No newline at end of file")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Diff<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let original_lines: Vec<_> = self.original.split_inclusive('\n').collect();
+        let modified_lines: Vec<_> = self.modified.split_inclusive('\n').collect();
+        let changes = diff::slice(&original_lines, &modified_lines);
+        let hunks = Self::hunks(&changes);
+        if hunks.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.path.to_str().ok_or(Error)?;
+        writeln!(f, "{}", format!("--- {path}").bold())?;
+        writeln!(f, "{}", format!("+++ {path}").bold())?;
+
+        // Line numbers of both sides preceding each change
+        let mut linenos = Vec::with_capacity(changes.len() + 1);
+        let (mut original_lineno, mut modified_lineno) = (0, 0);
+        for change in &changes {
+            linenos.push((original_lineno, modified_lineno));
+            match change {
+                Change::Left(_) => original_lineno += 1,
+                Change::Right(_) => modified_lineno += 1,
+                Change::Both(..) => {
+                    original_lineno += 1;
+                    modified_lineno += 1;
+                }
+            }
+        }
+        linenos.push((original_lineno, modified_lineno));
+
+        for hunk in hunks {
+            let (original_start, modified_start) = linenos[hunk.start];
+            let (original_end, modified_end) = linenos[hunk.end];
+            let header = format!(
+                "@@ -{} +{} @@",
+                Self::range(original_start, original_end - original_start),
+                Self::range(modified_start, modified_end - modified_start)
+            );
+            writeln!(f, "{}", header.cyan())?;
+
+            for change in &changes[hunk] {
+                match change {
+                    Change::Left(line) => Self::write_line(f, '-', line)?,
+                    Change::Right(line) => Self::write_line(f, '+', line)?,
+                    Change::Both(line, _) => Self::write_line(f, ' ', line)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use colored::control;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use super::*;
+
+    #[test]
+    #[serial(colored)]
+    fn display_fmt_plain() {
+        control::set_override(false);
+        let path = Path::new("file.md");
+        let original = "#Header\nText\n1\n2\n3\n4\n5\n6\n7\n8\n<http://example.com>\n";
+        let modified = "# Header\nText\n1\n2\n3\n4\n5\n6\n7\n8\nhttp://example.com\n";
+        let actual = Diff::new(path, original, modified).to_string();
+        let expected = indoc! {"
+            --- file.md
+            +++ file.md
+            @@ -1,4 +1,4 @@
+            -#Header
+            +# Header
+             Text
+             1
+             2
+            @@ -8,4 +8,4 @@
+             6
+             7
+             8
+            -<http://example.com>
+            +http://example.com
+        "};
+        assert_eq!(actual, expected);
+        control::unset_override();
+    }
+
+    #[test]
+    #[serial(colored)]
+    fn display_fmt_plain_no_newline_at_end_of_file() {
+        control::set_override(false);
+        let path = Path::new("file.md");
+        let actual = Diff::new(path, "Text", "Text\n").to_string();
+        let expected = indoc! {"
+            --- file.md
+            +++ file.md
+            @@ -1 +1 @@
+            -Text
+            \\ No newline at end of file
+            +Text
+        "};
+        assert_eq!(actual, expected);
+        control::unset_override();
+    }
+
+    #[test]
+    #[serial(colored)]
+    fn display_fmt_colorized() {
+        control::set_override(true);
+        let path = Path::new("file.md");
+        let actual = Diff::new(path, "Text  \n", "Text\n").to_string();
+        let expected = "\u{1b}[1m--- file.md\u{1b}[0m\n\u{1b}[1m+++ file.md\u{1b}[0m\n\u{1b}[36m@@ -1 +1 @@\u{1b}[0m\n\u{1b}[31m-Text  \u{1b}[0m\n\u{1b}[32m+Text\u{1b}[0m\n";
+        assert_eq!(actual, expected);
+        control::unset_override();
+    }
+
+    #[test]
+    fn display_fmt_no_changes() {
+        let path = Path::new("file.md");
+        let actual = Diff::new(path, "Text\n", "Text\n").to_string();
+        assert_eq!(actual, "");
+    }
+}
@@ -0,0 +1,197 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter;
+
+use colored::control::SHOULD_COLORIZE;
+use miette::{
+    Diagnostic, GraphicalReportHandler, GraphicalTheme, LabeledSpan, MietteError,
+    MietteSpanContents, SourceCode, SourceSpan, SpanContents,
+};
+
+use super::RULES_URI;
+use crate::Violation;
+use crate::rule::Severity;
+
+/// A violation rendered as a diagnostic with the offending source lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Full<'a> {
+    violation: &'a Violation,
+    source: &'a str,
+}
+
+impl<'a> Full<'a> {
+    /// `source` is the content of the file containing the violation
+    pub const fn new(violation: &'a Violation, source: &'a str) -> Self {
+        Self { violation, source }
+    }
+
+    // Converts a 1-based line and byte column of Sourcepos to a byte offset
+    fn offset(&self, line: usize, column: usize) -> usize {
+        let line_start: usize = self
+            .source
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum();
+        (line_start + column.saturating_sub(1)).min(self.source.len())
+    }
+
+    fn span(&self) -> SourceSpan {
+        let position = self.violation.position();
+        let start = self.offset(position.start.line, position.start.column);
+        // The end of Sourcepos is inclusive
+        let end = (self.offset(position.end.line, position.end.column) + 1).min(self.source.len());
+        SourceSpan::from((start, end.saturating_sub(start)))
+    }
+}
+
+impl Display for Full<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let theme = if SHOULD_COLORIZE.should_colorize() {
+            GraphicalTheme::unicode()
+        } else {
+            GraphicalTheme::unicode_nocolor()
+        };
+        let diagnostic = Snippet {
+            full: self,
+            name: self.violation.path().to_str().ok_or(fmt::Error)?,
+        };
+        GraphicalReportHandler::new_themed(theme)
+            .with_links(false)
+            .render_report(f, &diagnostic)
+    }
+}
+
+// Diagnostic borrowing the source, which `NamedSource` cannot do
+struct Snippet<'a> {
+    full: &'a Full<'a>,
+    name: &'a str,
+}
+
+impl Debug for Snippet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.full, f)
+    }
+}
+
+impl Display for Snippet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.full.violation.description())
+    }
+}
+
+impl Error for Snippet<'_> {}
+
+impl Diagnostic for Snippet<'_> {
+    fn code<'b>(&'b self) -> Option<Box<dyn Display + 'b>> {
+        let violation = self.full.violation;
+        Some(Box::new(format!(
+            "{}/{}",
+            violation.name(),
+            violation.alias()
+        )))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.full.violation.severity() {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Info => miette::Severity::Advice,
+        })
+    }
+
+    fn help<'b>(&'b self) -> Option<Box<dyn Display + 'b>> {
+        let name = self.full.violation.name().to_lowercase();
+        Some(Box::new(format!("See {RULES_URI}#{name}")))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(self)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = LabeledSpan::underline(self.full.span());
+        Some(Box::new(iter::once(label)))
+    }
+}
+
+impl SourceCode for Snippet<'_> {
+    fn read_span<'b>(
+        &'b self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'b> + 'b>, MietteError> {
+        let contents =
+            self.full
+                .source
+                .read_span(span, context_lines_before, context_lines_after)?;
+        Ok(Box::new(MietteSpanContents::new_named(
+            self.name.to_owned(),
+            contents.data(),
+            *contents.span(),
+            contents.line(),
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use colored::control;
+    use comrak::nodes::Sourcepos;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "MD026",
+        description: "Trailing punctuation in header",
+        aliases: &["no-trailing-punctuation"],
+        tags: &[Tag::Headers],
+    };
+
+    #[test]
+    #[serial(colored)]
+    fn display_fmt_plain() {
+        control::set_override(false);
+        let path = Path::new("file.md").to_path_buf();
+        let position = Sourcepos::from((3, 1, 3, 9));
+        let violation = Violation::new(path, &METADATA, position);
+        let source = "# Title\n\n## Hello.\n\nText\n";
+        let actual = Full::new(&violation, source).to_string();
+        // Empty lines of the snippet have a trailing space
+        let expected = [
+            "MD026/no-trailing-punctuation",
+            "",
+            "  × Trailing punctuation in header",
+            "   ╭─[file.md:3:1]",
+            " 2 │ ",
+            " 3 │ ## Hello.",
+            "   · ─────────",
+            " 4 │ ",
+            "   ╰────",
+            "  help: See https://github.com/markdownlint/markdownlint/blob/main/docs/RULES.md#md026",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(actual, expected);
+        control::unset_override();
+    }
+
+    #[test]
+    fn span() {
+        let path = Path::new("file.md").to_path_buf();
+        let position = Sourcepos::from((2, 3, 2, 4));
+        let violation = Violation::new(path, &METADATA, position);
+        let actual = Full::new(&violation, "abc\ndefg\n").span();
+        assert_eq!(actual, SourceSpan::from((6, 2)));
+    }
+}
@@ -0,0 +1,105 @@
+use core::cmp::Ordering;
+use core::fmt::{Display, Error, Formatter, Result};
+
+use crate::Violation;
+use crate::rule::Severity;
+
+/// A violation as a workflow command of GitHub Actions, shown as an annotation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Github<'a> {
+    violation: &'a Violation,
+}
+
+impl<'a> Github<'a> {
+    pub const fn new(violation: &'a Violation) -> Self {
+        Self { violation }
+    }
+
+    #[cfg(test)]
+    pub const fn violation(&self) -> &'a Violation {
+        self.violation
+    }
+
+    const fn command(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        }
+    }
+
+    fn escape_data(data: &str) -> String {
+        data.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    fn escape_property(property: &str) -> String {
+        Self::escape_data(property)
+            .replace(':', "%3A")
+            .replace(',', "%2C")
+    }
+}
+
+impl Display for Github<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let path = self.violation.path().to_str().ok_or(Error)?;
+        let position = self.violation.position();
+        write!(
+            f,
+            "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+            Self::command(self.violation.severity()),
+            Self::escape_property(path.strip_prefix("./").unwrap_or(path)),
+            position.start.line,
+            position.start.column,
+            position.end.line,
+            position.end.column,
+            Self::escape_property(self.violation.name()),
+            Self::escape_data(self.violation.description())
+        )
+    }
+}
+
+impl PartialOrd for Github<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Github<'_> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.violation.cmp(other.violation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comrak::nodes::Sourcepos;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "name",
+        description: "100% description",
+        aliases: &["alias"],
+        tags: &[Tag::Atx],
+    };
+
+    #[test]
+    fn display_fmt() {
+        let path = Path::new("./docs/a,b.md").to_path_buf();
+        let position = Sourcepos::from((1, 2, 3, 4));
+        let violation = Violation::new(path, &METADATA, position);
+        let actual = Github::new(&violation).to_string();
+        let expected = "::error file=docs/a%2Cb.md,line=1,col=2,endLine=3,endColumn=4,title=name::100%25 description";
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,158 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::fmt::{Display, Error, Formatter, Result};
+use std::path::PathBuf;
+
+use serde_json::{Value, json};
+use xxhash_rust::xxh3::xxh3_128;
+
+use crate::Violation;
+use crate::rule::Severity;
+
+/// Violations as a GitLab Code Quality report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gitlab<'a> {
+    violations: &'a [Violation],
+    sources: &'a BTreeMap<PathBuf, String>,
+}
+
+impl<'a> Gitlab<'a> {
+    /// `sources` are the contents of the files, used to compute fingerprints
+    pub const fn new(violations: &'a [Violation], sources: &'a BTreeMap<PathBuf, String>) -> Self {
+        Self {
+            violations,
+            sources,
+        }
+    }
+
+    const fn severity(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "major",
+            Severity::Warning => "minor",
+            Severity::Info => "info",
+        }
+    }
+
+    // Fingerprints depend on the content of the lines instead of their numbers,
+    // so that they do not change when unrelated lines are added or removed
+    fn fingerprint(path: &str, rule: &str, content: u64, occurrence: usize) -> String {
+        let key = format!("{path}\0{rule}\0{content:016x}\0{occurrence}");
+        format!("{:032x}", xxh3_128(key.as_bytes()))
+    }
+}
+
+impl Display for Gitlab<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut occurrences: BTreeMap<String, usize> = BTreeMap::new();
+        let mut issues = vec![];
+        for violation in self.violations {
+            let path = violation.path().to_str().ok_or(Error)?;
+            let path = path.strip_prefix("./").unwrap_or(path);
+            let line_number = violation.position().start.line;
+            let source = self
+                .sources
+                .get(violation.path())
+                .map_or("", String::as_str);
+            let content = violation.fingerprint(source);
+
+            // Identical lines violating the same rule are told apart by their order
+            let base = Self::fingerprint(path, violation.name(), content, 0);
+            let occurrence = occurrences.entry(base).or_default();
+            let fingerprint = Self::fingerprint(path, violation.name(), content, *occurrence);
+            *occurrence += 1;
+
+            issues.push(json!({
+                "description": format!("{}/{} {}", violation.name(), violation.alias(), violation.description()),
+                "check_name": violation.name(),
+                "fingerprint": fingerprint,
+                "severity": Self::severity(violation.severity()),
+                "location": {
+                    "path": path,
+                    "lines": { "begin": line_number },
+                },
+            }));
+        }
+
+        let json = serde_json::to_string_pretty(&Value::Array(issues)).map_err(|_err| Error)?;
+        write!(f, "{json}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+    use std::path::Path;
+
+    use comrak::nodes::Sourcepos;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use xxhash_rust::xxh3::xxh3_64;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "name",
+        description: "description",
+        aliases: &["alias"],
+        tags: &[Tag::Atx],
+    };
+
+    fn issues(violations: &[Violation], text: &str) -> Vec<Value> {
+        let mut sources = BTreeMap::new();
+        sources.insert(Path::new("./file.md").to_path_buf(), text.to_owned());
+        let json = Gitlab::new(violations, &sources).to_string();
+        serde_json::from_str::<Vec<Value>>(&json).unwrap_or_default()
+    }
+
+    #[test]
+    fn display_fmt() {
+        let path = Path::new("./file.md").to_path_buf();
+        let violation = Violation::new(path, &METADATA, Sourcepos::from((2, 1, 2, 5)));
+        let actual = issues(&[violation], "# Title\nline\n");
+        let fingerprint = Gitlab::fingerprint("file.md", "name", xxh3_64(b"line"), 0);
+        let expected = vec![json!({
+            "description": "name/alias description",
+            "check_name": "name",
+            "fingerprint": fingerprint,
+            "severity": "major",
+            "location": {
+                "path": "file.md",
+                "lines": { "begin": 2 },
+            },
+        })];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn display_fmt_stable_fingerprints() {
+        let path = Path::new("./file.md").to_path_buf();
+        let violation = Violation::new(path.clone(), &METADATA, Sourcepos::from((2, 1, 2, 5)));
+        let moved_violation =
+            Violation::new(path.clone(), &METADATA, Sourcepos::from((3, 1, 3, 5)));
+        let duplicated_violation = Violation::new(path, &METADATA, Sourcepos::from((4, 1, 4, 5)));
+
+        let original = issues(&[violation], "# Title\nline\n");
+        let moved = issues(slice::from_ref(&moved_violation), "# Title\n\nline\n");
+        assert_eq!(original[0]["fingerprint"], moved[0]["fingerprint"]);
+
+        let duplicated = issues(
+            &[moved_violation, duplicated_violation],
+            "# Title\n\nline\nline\n",
+        );
+        assert_eq!(original[0]["fingerprint"], duplicated[0]["fingerprint"]);
+        assert_ne!(duplicated[0]["fingerprint"], duplicated[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn fingerprint() {
+        // Must not change between versions, or GitLab reports all issues as new
+        let content = xxh3_64(b"text");
+        let actual = Gitlab::fingerprint("file.md", "MD013", content, 0);
+        assert_eq!(actual, "bcaa581e9e9a333f349fd35bbf036480");
+        assert_ne!(actual, Gitlab::fingerprint("file.md", "MD013", content, 1));
+        assert_ne!(actual, Gitlab::fingerprint("file.md", "MD009", content, 0));
+    }
+}
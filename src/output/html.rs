@@ -0,0 +1,242 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::fmt::{Display, Error, Formatter, Result};
+use std::path::{Path, PathBuf};
+
+use super::{RULES_URI, escape_xml as escape, severity_label, summarize};
+use crate::Violation;
+
+// Number of lines shown before and after the offending lines
+const CONTEXT_LINES: usize = 2;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2rem auto; max-width: 60rem; color: #24292f; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d0d7de; padding: 0.25rem 0.75rem; text-align: left; }
+section { margin-bottom: 2rem; }
+.violation { margin: 1rem 0; }
+.rule { font-weight: bold; color: #cf222e; }
+.warning .rule { color: #9a6700; }
+.info .rule { color: #0969da; }
+.location { color: #57606a; }
+pre { background: #f6f8fa; padding: 0.5rem; overflow-x: auto; }
+.line { display: block; }
+.line.highlight { background: #ffebe9; }
+.number { display: inline-block; width: 3rem; color: #57606a; user-select: none; }
+";
+
+/// All violations as a self-contained HTML report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Html<'a> {
+    files: &'a [PathBuf],
+    violations: &'a [Violation],
+    sources: &'a BTreeMap<PathBuf, String>,
+}
+
+impl<'a> Html<'a> {
+    /// `violations` must be sorted by path, and `sources` are the contents of the files
+    pub const fn new(
+        files: &'a [PathBuf],
+        violations: &'a [Violation],
+        sources: &'a BTreeMap<PathBuf, String>,
+    ) -> Self {
+        Self {
+            files,
+            violations,
+            sources,
+        }
+    }
+
+    fn group_by_file(violations: &[Violation]) -> impl Iterator<Item = &[Violation]> {
+        violations.chunk_by(|a, b| a.path() == b.path())
+    }
+
+    fn fmt_summary(&self, f: &mut Formatter<'_>) -> Result {
+        let mut counts: BTreeMap<&str, (&Violation, usize)> = BTreeMap::new();
+        for violation in self.violations {
+            counts.entry(violation.name()).or_insert((violation, 0)).1 += 1;
+        }
+
+        let num_files = Self::group_by_file(self.violations).count();
+        writeln!(
+            f,
+            "<p>Found {} in {num_files} of {} files.</p>",
+            summarize(self.violations),
+            self.files.len()
+        )?;
+        if counts.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "<h2>Rules</h2>")?;
+        writeln!(f, "<table>")?;
+        writeln!(
+            f,
+            "<thead><tr><th>Rule</th><th>Description</th><th>Severity</th><th>Count</th></tr></thead>"
+        )?;
+        writeln!(f, "<tbody>")?;
+        for (name, (violation, count)) in counts {
+            writeln!(
+                f,
+                r#"<tr><td><a href="{RULES_URI}#{}">{}</a></td><td>{}</td><td>{}</td><td>{count}</td></tr>"#,
+                name.to_lowercase(),
+                escape(name),
+                escape(violation.description()),
+                violation.severity()
+            )?;
+        }
+        writeln!(f, "</tbody>")?;
+        writeln!(f, "</table>")
+    }
+
+    fn fmt_violation(&self, f: &mut Formatter<'_>, violation: &Violation) -> Result {
+        let position = violation.position();
+        writeln!(f, r#"<div class="violation {}">"#, violation.severity())?;
+        writeln!(
+            f,
+            r#"<p><span class="rule">{}/{}</span> {}{} <span class="location">{}:{}</span></p>"#,
+            escape(violation.name()),
+            escape(violation.alias()),
+            severity_label(violation.severity()),
+            escape(violation.description()),
+            position.start.line,
+            position.start.column
+        )?;
+
+        let Some(source) = self.sources.get(violation.path()) else {
+            return writeln!(f, "</div>");
+        };
+
+        let first = position.start.line.saturating_sub(CONTEXT_LINES).max(1);
+        let last = position.end.line.max(position.start.line) + CONTEXT_LINES;
+        write!(f, "<pre><code>")?;
+        for (index, line) in source.lines().enumerate() {
+            let number = index + 1;
+            if number < first || number > last {
+                continue;
+            }
+
+            let class = if (position.start.line..=position.end.line).contains(&number) {
+                "line highlight"
+            } else {
+                "line"
+            };
+            write!(
+                f,
+                r#"<span class="{class}"><span class="number">{number}</span>{}</span>"#,
+                escape(line)
+            )?;
+        }
+        writeln!(f, "</code></pre>")?;
+        writeln!(f, "</div>")
+    }
+
+    fn fmt_file(&self, f: &mut Formatter<'_>, path: &Path, violations: &[Violation]) -> Result {
+        let path = escape(path.to_str().ok_or(Error)?);
+        writeln!(f, r#"<section id="{path}">"#)?;
+        writeln!(f, "<h3>{path} ({})</h3>", violations.len())?;
+        for violation in violations {
+            self.fmt_violation(f, violation)?;
+        }
+        writeln!(f, "</section>")
+    }
+}
+
+impl Display for Html<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, r#"<html lang="en">"#)?;
+        writeln!(f, "<head>")?;
+        writeln!(f, r#"<meta charset="utf-8">"#)?;
+        writeln!(f, "<title>Mado report</title>")?;
+        writeln!(f, "<style>{STYLE}</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<h1>Mado report</h1>")?;
+        self.fmt_summary(f)?;
+
+        if !self.violations.is_empty() {
+            writeln!(f, "<h2>Files</h2>")?;
+        }
+        for file_violations in Self::group_by_file(self.violations) {
+            let path = file_violations.first().map(Violation::path).ok_or(Error)?;
+            self.fmt_file(f, path, file_violations)?;
+        }
+
+        writeln!(f, "</body>")?;
+        write!(f, "</html>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comrak::nodes::Sourcepos;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "MD026",
+        description: "Trailing punctuation in header",
+        aliases: &["no-trailing-punctuation"],
+        tags: &[Tag::Headers],
+    };
+
+    fn body(html: &str) -> &str {
+        let start = html.find("<h1>").unwrap_or_default();
+        let end = html.find("</body>").unwrap_or(html.len());
+        &html[start..end]
+    }
+
+    #[test]
+    fn display_fmt() {
+        let path = Path::new("<a>.md").to_path_buf();
+        let files = [path.clone(), Path::new("b.md").to_path_buf()];
+        let violations = [Violation::new(
+            path.clone(),
+            &METADATA,
+            Sourcepos::from((4, 1, 4, 9)),
+        )];
+        let mut sources = BTreeMap::new();
+        sources.insert(path, "# Title\n\nText\n## Hello.\n\nA\nB\n".to_owned());
+        let actual = Html::new(&files, &violations, &sources).to_string();
+        let expected = indoc! {r#"
+            <h1>Mado report</h1>
+            <p>Found 1 error in 1 of 2 files.</p>
+            <h2>Rules</h2>
+            <table>
+            <thead><tr><th>Rule</th><th>Description</th><th>Severity</th><th>Count</th></tr></thead>
+            <tbody>
+            <tr><td><a href="https://github.com/markdownlint/markdownlint/blob/main/docs/RULES.md#md026">MD026</a></td><td>Trailing punctuation in header</td><td>error</td><td>1</td></tr>
+            </tbody>
+            </table>
+            <h2>Files</h2>
+            <section id="&lt;a&gt;.md">
+            <h3>&lt;a&gt;.md (1)</h3>
+            <div class="violation error">
+            <p><span class="rule">MD026/no-trailing-punctuation</span> Trailing punctuation in header <span class="location">4:1</span></p>
+            <pre><code><span class="line"><span class="number">2</span></span><span class="line"><span class="number">3</span>Text</span><span class="line highlight"><span class="number">4</span>## Hello.</span><span class="line"><span class="number">5</span></span><span class="line"><span class="number">6</span>A</span></code></pre>
+            </div>
+            </section>
+        "#};
+        assert!(actual.starts_with("<!DOCTYPE html>"));
+        assert!(actual.ends_with("</html>"));
+        assert_eq!(body(&actual), expected);
+    }
+
+    #[test]
+    fn display_fmt_empty() {
+        let files = [Path::new("a.md").to_path_buf()];
+        let actual = Html::new(&files, &[], &BTreeMap::new()).to_string();
+        let expected = indoc! {"
+            <h1>Mado report</h1>
+            <p>Found 0 errors in 0 of 1 files.</p>
+        "};
+        assert_eq!(body(&actual), expected);
+    }
+}
@@ -0,0 +1,246 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use core::fmt::{Display, Error, Formatter, Result};
+use std::path::{Path, PathBuf};
+
+use comrak::nodes::LineColumn;
+use serde::Serialize;
+
+use crate::Violation;
+use crate::rule::{Severity, Tag};
+
+/// Version of the JSON schema, incremented on breaking changes
+const VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl From<LineColumn> for Position {
+    fn from(line_column: LineColumn) -> Self {
+        Self {
+            line: line_column.line,
+            column: line_column.column,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Range {
+    start: Position,
+    end: Position,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    path: &'a str,
+    rule: &'a str,
+    alias: &'a str,
+    severity: Severity,
+    description: &'a str,
+    tags: &'a [Tag],
+    position: Range,
+}
+
+impl<'a> Record<'a> {
+    fn new(violation: &'a Violation) -> Option<Self> {
+        Some(Self {
+            path: violation.path().to_str()?,
+            rule: violation.name(),
+            alias: violation.alias(),
+            severity: violation.severity(),
+            description: violation.description(),
+            tags: violation.tags(),
+            position: Range {
+                start: violation.position().start.into(),
+                end: violation.position().end.into(),
+            },
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Summary {
+    violations: usize,
+    files: usize,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    version: u32,
+    violations: Vec<Record<'a>>,
+    summary: Summary,
+}
+
+/// All violations as a single JSON document with a summary, which counts all checked files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json<'a> {
+    files: &'a [PathBuf],
+    violations: &'a [Violation],
+}
+
+impl<'a> Json<'a> {
+    pub const fn new(files: &'a [PathBuf], violations: &'a [Violation]) -> Self {
+        Self { files, violations }
+    }
+}
+
+impl Display for Json<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let records = self
+            .violations
+            .iter()
+            .map(Record::new)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error)?;
+        let files: BTreeSet<&Path> = self
+            .files
+            .iter()
+            .chain(self.violations.iter().map(Violation::path))
+            .map(PathBuf::as_path)
+            .collect();
+        let report = Report {
+            version: VERSION,
+            violations: records,
+            summary: Summary {
+                violations: self.violations.len(),
+                files: files.len(),
+            },
+        };
+        let json = serde_json::to_string_pretty(&report).map_err(|_err| Error)?;
+        write!(f, "{json}")
+    }
+}
+
+/// A violation as a single line of JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonLines<'a> {
+    violation: &'a Violation,
+}
+
+impl<'a> JsonLines<'a> {
+    pub const fn new(violation: &'a Violation) -> Self {
+        Self { violation }
+    }
+}
+
+impl Display for JsonLines<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let record = Record::new(self.violation).ok_or(Error)?;
+        let json = serde_json::to_string(&record).map_err(|_err| Error)?;
+        write!(f, "{json}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comrak::nodes::Sourcepos;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::Metadata;
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "name",
+        description: "description",
+        aliases: &["alias"],
+        tags: &[Tag::Atx, Tag::HardTab],
+    };
+
+    #[test]
+    fn json_display_fmt() {
+        let path = Path::new("file.md").to_path_buf();
+        let violation1 = Violation::new(path.clone(), &METADATA, Sourcepos::from((1, 1, 3, 5)));
+        let violation2 = Violation::new(path.clone(), &METADATA, Sourcepos::from((4, 2, 4, 2)));
+        // Files without violations are also counted
+        let files = vec![path, Path::new("other.md").to_path_buf()];
+        let actual = Json::new(&files, &[violation1, violation2]).to_string();
+        let expected = indoc! {r#"
+            {
+              "version": 1,
+              "violations": [
+                {
+                  "path": "file.md",
+                  "rule": "name",
+                  "alias": "alias",
+                  "severity": "error",
+                  "description": "description",
+                  "tags": [
+                    "atx",
+                    "hard-tab"
+                  ],
+                  "position": {
+                    "start": {
+                      "line": 1,
+                      "column": 1
+                    },
+                    "end": {
+                      "line": 3,
+                      "column": 5
+                    }
+                  }
+                },
+                {
+                  "path": "file.md",
+                  "rule": "name",
+                  "alias": "alias",
+                  "severity": "error",
+                  "description": "description",
+                  "tags": [
+                    "atx",
+                    "hard-tab"
+                  ],
+                  "position": {
+                    "start": {
+                      "line": 4,
+                      "column": 2
+                    },
+                    "end": {
+                      "line": 4,
+                      "column": 2
+                    }
+                  }
+                }
+              ],
+              "summary": {
+                "violations": 2,
+                "files": 2
+              }
+            }"#};
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn json_display_fmt_empty() {
+        let actual = Json::new(&[], &[]).to_string();
+        let expected = indoc! {r#"
+            {
+              "version": 1,
+              "violations": [],
+              "summary": {
+                "violations": 0,
+                "files": 0
+              }
+            }"#};
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn json_lines_display_fmt() {
+        let path = Path::new("file.md").to_path_buf();
+        let position = Sourcepos::from((0, 1, 3, 5));
+        let violation = Violation::new(path, &METADATA, position);
+        let actual = JsonLines::new(&violation).to_string();
+        let expected = r#"{"path":"file.md","rule":"name","alias":"alias","severity":"error","description":"description","tags":["atx","hard-tab"],"position":{"start":{"line":0,"column":1},"end":{"line":3,"column":5}}}"#;
+        assert_eq!(actual, expected);
+    }
+}
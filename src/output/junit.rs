@@ -0,0 +1,176 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::fmt::{Display, Error, Formatter, Result};
+use std::path::{Path, PathBuf};
+
+use super::escape_xml as escape;
+use crate::Violation;
+use crate::rule::Severity;
+
+/// All checked files as a `JUnit` XML report, where each file is a testcase
+/// and each error is a failure of it. Warnings and info messages are written to the output
+/// of the testcase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Junit<'a> {
+    files: &'a [PathBuf],
+    violations: &'a [Violation],
+}
+
+impl<'a> Junit<'a> {
+    pub const fn new(files: &'a [PathBuf], violations: &'a [Violation]) -> Self {
+        Self { files, violations }
+    }
+}
+
+impl Display for Junit<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut testcases: BTreeMap<&Path, Vec<&Violation>> = self
+            .files
+            .iter()
+            .map(|path| (path.as_path(), vec![]))
+            .collect();
+        for violation in self.violations {
+            testcases
+                .entry(violation.path())
+                .or_default()
+                .push(violation);
+        }
+
+        let num_tests = testcases.len();
+        let num_failures = testcases
+            .values()
+            .filter(|violations| {
+                violations
+                    .iter()
+                    .any(|violation| violation.severity() == Severity::Error)
+            })
+            .count();
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<testsuites name="mado" tests="{num_tests}" failures="{num_failures}" errors="0">"#
+        )?;
+        writeln!(
+            f,
+            r#"  <testsuite name="mado" tests="{num_tests}" failures="{num_failures}" errors="0" skipped="0">"#
+        )?;
+        for (path, violations) in testcases {
+            let name = escape(path.to_str().ok_or(Error)?);
+            if violations.is_empty() {
+                writeln!(f, r#"    <testcase name="{name}" classname="{name}"/>"#)?;
+                continue;
+            }
+
+            writeln!(f, r#"    <testcase name="{name}" classname="{name}">"#)?;
+            let (errors, others): (Vec<_>, Vec<_>) = violations
+                .into_iter()
+                .partition(|violation| violation.severity() == Severity::Error);
+            for violation in errors {
+                let line = violation.position().start.line;
+                let column = violation.position().start.column;
+                let rule = escape(violation.name());
+                let description = escape(violation.description());
+                writeln!(
+                    f,
+                    r#"      <failure message="{rule} {description}" type="{rule}">{name}:{line}:{column}: {rule} {description}</failure>"#
+                )?;
+            }
+            if !others.is_empty() {
+                write!(f, "      <system-out>")?;
+                for violation in others {
+                    writeln!(
+                        f,
+                        "{name}:{}:{}: {} [{}] {}",
+                        violation.position().start.line,
+                        violation.position().start.column,
+                        escape(violation.name()),
+                        violation.severity(),
+                        escape(violation.description())
+                    )?;
+                }
+                writeln!(f, "</system-out>")?;
+            }
+            writeln!(f, "    </testcase>")?;
+        }
+        writeln!(f, "  </testsuite>")?;
+        write!(f, "</testsuites>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comrak::nodes::Sourcepos;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::{Metadata, Tag};
+
+    use super::*;
+
+    const METADATA: Metadata = Metadata {
+        name: "name",
+        description: "<description>",
+        aliases: &["alias"],
+        tags: &[Tag::Atx],
+    };
+
+    #[test]
+    fn display_fmt() {
+        let path1 = Path::new("a.md").to_path_buf();
+        let path2 = Path::new("b&c.md").to_path_buf();
+        let files = [path1.clone(), path2];
+        let violation1 = Violation::new(path1.clone(), &METADATA, Sourcepos::from((1, 1, 1, 5)));
+        let violation2 = Violation::new(path1, &METADATA, Sourcepos::from((3, 2, 3, 5)));
+        let actual = Junit::new(&files, &[violation1, violation2]).to_string();
+        let expected = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites name="mado" tests="2" failures="1" errors="0">
+              <testsuite name="mado" tests="2" failures="1" errors="0" skipped="0">
+                <testcase name="a.md" classname="a.md">
+                  <failure message="name &lt;description&gt;" type="name">a.md:1:1: name &lt;description&gt;</failure>
+                  <failure message="name &lt;description&gt;" type="name">a.md:3:2: name &lt;description&gt;</failure>
+                </testcase>
+                <testcase name="b&amp;c.md" classname="b&amp;c.md"/>
+              </testsuite>
+            </testsuites>"#};
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn display_fmt_warnings() {
+        let path = Path::new("a.md").to_path_buf();
+        let files = [path.clone()];
+        let warning = Violation::new(path.clone(), &METADATA, Sourcepos::from((1, 1, 1, 5)))
+            .with_severity(Severity::Warning);
+        let info = Violation::new(path, &METADATA, Sourcepos::from((3, 2, 3, 5)))
+            .with_severity(Severity::Info);
+        let actual = Junit::new(&files, &[warning, info]).to_string();
+        let expected = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites name="mado" tests="1" failures="0" errors="0">
+              <testsuite name="mado" tests="1" failures="0" errors="0" skipped="0">
+                <testcase name="a.md" classname="a.md">
+                  <system-out>a.md:1:1: name [warning] &lt;description&gt;
+            a.md:3:2: name [info] &lt;description&gt;
+            </system-out>
+                </testcase>
+              </testsuite>
+            </testsuites>"#};
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn display_fmt_empty() {
+        let actual = Junit::new(&[], &[]).to_string();
+        let expected = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites name="mado" tests="0" failures="0" errors="0">
+              <testsuite name="mado" tests="0" failures="0" errors="0" skipped="0">
+              </testsuite>
+            </testsuites>"#};
+        assert_eq!(actual, expected);
+    }
+}
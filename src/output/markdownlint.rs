@@ -1,8 +1,7 @@
 use core::cmp::Ordering;
 use core::fmt::{Display, Error, Formatter, Result};
 
-use colored::Colorize as _;
-
+use super::{colorize, severity_label};
 use crate::Violation;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,20 +25,17 @@ impl Display for Markdownlint<'_> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let path = self.violation.path().to_str().ok_or(Error)?;
-        write!(
-            f,
-            "{}",
-            format!(
-                "{}:{}:{} {}/{} {}",
-                path,
-                self.violation.position().start.line,
-                self.violation.position().start.column,
-                self.violation.name(),
-                self.violation.alias(),
-                self.violation.description()
-            )
-            .red()
-        )
+        let line = format!(
+            "{}:{}:{} {}/{} {}{}",
+            path,
+            self.violation.position().start.line,
+            self.violation.position().start.column,
+            self.violation.name(),
+            self.violation.alias(),
+            severity_label(self.violation.severity()),
+            self.violation.description()
+        );
+        write!(f, "{}", colorize(&line, self.violation.severity()))
     }
 }
 
@@ -0,0 +1,205 @@
+use core::fmt::{Display, Error, Formatter, Result};
+use std::sync::LazyLock;
+
+use serde_json::{Value, json};
+
+use super::RULES_URI;
+use crate::Violation;
+use crate::config::Lint;
+use crate::rule::{Metadata, Rule, Severity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Metadata of all supported rules and suppression comments, used as `tool.driver.rules`
+static RULES: LazyLock<Vec<&'static Metadata>> = LazyLock::new(|| {
+    Vec::<Rule>::from(&Lint::default())
+        .iter()
+        .map(Rule::metadata)
+        .chain(Metadata::SUPPRESSIONS)
+        .collect()
+});
+
+/// All violations as a SARIF 2.1.0 log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sarif<'a> {
+    violations: &'a [Violation],
+}
+
+impl<'a> Sarif<'a> {
+    pub const fn new(violations: &'a [Violation]) -> Self {
+        Self { violations }
+    }
+
+    fn rule(metadata: &Metadata) -> Value {
+        json!({
+            "id": metadata.name,
+            "name": metadata.aliases.first(),
+            "shortDescription": { "text": metadata.description },
+            "helpUri": format!("{RULES_URI}#{}", metadata.name.to_lowercase()),
+            "properties": {
+                "aliases": metadata.aliases,
+                "tags": metadata.tags,
+            },
+        })
+    }
+
+    const fn level(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
+    fn result(violation: &Violation) -> Option<Value> {
+        let path = violation.path().to_str()?;
+        // Artifact locations are relative URIs, so "./" and backslashes are not allowed
+        let uri = path.strip_prefix("./").unwrap_or(path).replace('\\', "/");
+        let position = violation.position();
+        let mut result = json!({
+            "ruleId": violation.name(),
+            "level": Self::level(violation.severity()),
+            "message": { "text": violation.description() },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": {
+                        "startLine": position.start.line.max(1),
+                        "startColumn": position.start.column.max(1),
+                        "endLine": position.end.line.max(position.start.line).max(1),
+                        // Sourcepos is inclusive, while SARIF regions exclude the end column
+                        "endColumn": position.end.column.max(position.start.column).max(1) + 1,
+                    },
+                },
+            }],
+        });
+        // `ruleIndex` must be an integer, so it is omitted for unknown rules
+        if let Some(rule_index) = RULES
+            .iter()
+            .position(|metadata| metadata.name == violation.name())
+        {
+            result["ruleIndex"] = json!(rule_index);
+        }
+        Some(result)
+    }
+}
+
+impl Display for Sarif<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let rules: Vec<_> = RULES.iter().map(|metadata| Self::rule(metadata)).collect();
+        let results = self
+            .violations
+            .iter()
+            .map(Self::result)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error)?;
+        let log = json!({
+            "$schema": SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        });
+        let json = serde_json::to_string_pretty(&log).map_err(|_err| Error)?;
+        write!(f, "{json}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comrak::nodes::Sourcepos;
+    use pretty_assertions::assert_eq;
+
+    use crate::rule::RuleLike as _;
+    use crate::rule::{MD001, MD026};
+
+    use super::*;
+
+    #[test]
+    fn display_fmt() -> miette::Result<()> {
+        let path = Path::new("./docs/file.md").to_path_buf();
+        let violation1 = MD001::new().to_violation(path.clone(), Sourcepos::from((3, 1, 3, 9)));
+        let violation2 = MD026::default().to_violation(path, Sourcepos::from((1, 1, 1, 8)));
+        let actual: Value =
+            serde_json::from_str(&Sarif::new(&[violation1, violation2]).to_string())
+                .map_err(|err| miette::miette!(err))?;
+
+        assert_eq!(actual["version"], "2.1.0");
+        let run = &actual["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "mado");
+        let rules = run["tool"]["driver"]["rules"]
+            .as_array()
+            .map(Vec::len)
+            .unwrap_or_default();
+        assert_eq!(rules, RULES.len());
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0],
+            json!({
+                "id": "MD001",
+                "name": "header-increment",
+                "shortDescription": { "text": "Header levels should only increment by one level at a time" },
+                "helpUri": "https://github.com/markdownlint/markdownlint/blob/main/docs/RULES.md#md001",
+                "properties": {
+                    "aliases": ["header-increment"],
+                    "tags": ["headers"],
+                },
+            })
+        );
+        assert_eq!(
+            run["results"][1],
+            json!({
+                "ruleId": "MD026",
+                "ruleIndex": RULES.iter().position(|metadata| metadata.name == "MD026"),
+                "level": "error",
+                "message": { "text": "Trailing punctuation in header" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "docs/file.md" },
+                        "region": {
+                            "startLine": 1,
+                            "startColumn": 1,
+                            "endLine": 1,
+                            "endColumn": 9,
+                        },
+                    },
+                }],
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn display_fmt_unused_suppression() -> miette::Result<()> {
+        let path = Path::new("file.md").to_path_buf();
+        let violation = Violation::new(
+            path,
+            &Metadata::UNUSED_SUPPRESSION,
+            Sourcepos::from((1, 1, 1, 40)),
+        );
+        let actual: Value = serde_json::from_str(&Sarif::new(&[violation]).to_string())
+            .map_err(|err| miette::miette!(err))?;
+
+        let run = &actual["runs"][0];
+        let rule_index = RULES
+            .iter()
+            .position(|metadata| metadata.name == "unused-suppression");
+        assert_eq!(run["results"][0]["ruleIndex"], json!(rule_index));
+        assert!(run["results"][0]["ruleIndex"].is_u64());
+        assert_eq!(
+            run["tool"]["driver"]["rules"][rule_index.unwrap_or_default()]["id"],
+            "unused-suppression"
+        );
+        Ok(())
+    }
+}
@@ -45,6 +45,7 @@ mod md041;
 pub mod md046;
 mod md047;
 mod metadata;
+mod severity;
 mod tag;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -137,7 +138,7 @@ impl Rule {
 
     #[inline]
     #[must_use]
-    pub fn metadata(&self) -> &Metadata {
+    pub fn metadata(&self) -> &'static Metadata {
         match self {
             Self::MD001(rule) => rule.metadata(),
             Self::MD002(rule) => rule.metadata(),
@@ -232,4 +233,5 @@ pub use md041::MD041;
 pub use md046::MD046;
 pub use md047::MD047;
 pub use metadata::Metadata;
+pub use severity::Severity;
 pub use tag::Tag;
@@ -1,7 +1,10 @@
-use comrak::nodes::Sourcepos;
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use comrak::nodes::{NodeValue, Sourcepos};
 use miette::Result;
 
-use crate::{Document, violation::Violation};
+use crate::{Document, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -32,6 +35,13 @@ impl RuleLike for MD009 {
 
     #[inline]
     fn check(&self, doc: &Document) -> Result<Vec<Violation>> {
+        let mut hard_break_lines = BTreeSet::new();
+        for node in doc.ast.descendants() {
+            if node.data.borrow().value == NodeValue::LineBreak {
+                hard_break_lines.insert(node.data.borrow().sourcepos.start.line);
+            }
+        }
+
         let mut violations = vec![];
         for (i, line) in doc.lines.iter().enumerate() {
             let trimmed_line = line.trim_end_matches(' ');
@@ -39,7 +49,17 @@ impl RuleLike for MD009 {
                 let lineno = i + 1;
                 let position =
                     Sourcepos::from((lineno, trimmed_line.len() + 1, lineno, line.len()));
-                let violation = self.to_violation(doc.path.clone(), position);
+                let mut violation = self.to_violation(doc.path.clone(), position);
+                if let Some(start) = doc.offset(lineno, trimmed_line.len() + 1) {
+                    let end = start + line.len() - trimmed_line.len();
+                    // Hard line breaks are kept as backslashes, which render the same
+                    let edit = if hard_break_lines.contains(&lineno) {
+                        Edit::new(start..end, "\\".to_owned())
+                    } else {
+                        Edit::deletion(start..end)
+                    };
+                    violation = violation.with_fixes(vec![edit]);
+                }
                 violations.push(violation);
             }
         }
@@ -71,8 +91,40 @@ mod tests {
         let rule = MD009::new();
         let actual = rule.check(&doc)?;
         let expected = vec![
-            rule.to_violation(path.clone(), Sourcepos::from((1, 27, 1, 27))),
-            rule.to_violation(path, Sourcepos::from((2, 35, 2, 37))),
+            rule.to_violation(path.clone(), Sourcepos::from((1, 27, 1, 27)))
+                .with_fixes(vec![Edit::deletion(26..27)]),
+            rule.to_violation(path, Sourcepos::from((2, 35, 2, 37)))
+                .with_fixes(vec![Edit::deletion(62..65)]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn check_errors_with_hard_line_break() -> Result<()> {
+        let text = indoc! {"
+            Text with a hard line break  
+            Next line  
+
+            ```
+            Code with trailing spaces  
+            ```
+        "}
+        .to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path, text)?;
+        let rule = MD009::new();
+        let actual: Vec<Edit> = rule
+            .check(&doc)?
+            .iter()
+            .flat_map(Violation::fixes)
+            .cloned()
+            .collect();
+        let expected = vec![
+            Edit::new(27..29, "\\".to_owned()),
+            Edit::deletion(39..41),
+            Edit::deletion(72..74),
         ];
         assert_eq!(actual, expected);
         Ok(())
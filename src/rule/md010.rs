@@ -1,7 +1,9 @@
-use comrak::nodes::Sourcepos;
+use comrak::nodes::{NodeValue, Sourcepos};
 use miette::Result;
 
 use crate::Document;
+use crate::collection::RangeSet;
+use crate::fix::Edit;
 use crate::violation::Violation;
 
 use super::{Metadata, RuleLike, Tag};
@@ -11,6 +13,8 @@ use super::{Metadata, RuleLike, Tag};
 pub struct MD010;
 
 impl MD010 {
+    const TAB_SIZE: usize = 4;
+
     const METADATA: Metadata = Metadata {
         name: "MD010",
         description: "Hard tabs",
@@ -23,6 +27,24 @@ impl MD010 {
     pub const fn new() -> Self {
         Self {}
     }
+
+    // NOTE: Tabs are expanded to the next tab stop so that indentation keeps its meaning
+    fn expand_tabs(line: &str, line_offset: usize) -> Vec<Edit> {
+        let mut edits = vec![];
+        let mut width: usize = 0;
+        for (idx, c) in line.char_indices() {
+            if c == '\t' {
+                let spaces = (width + 1).next_multiple_of(Self::TAB_SIZE) - width;
+                let start = line_offset + idx;
+                edits.push(Edit::new(start..start + 1, " ".repeat(spaces)));
+                width += spaces;
+            } else {
+                width += 1;
+            }
+        }
+
+        edits
+    }
 }
 
 impl RuleLike for MD010 {
@@ -31,14 +53,29 @@ impl RuleLike for MD010 {
         &Self::METADATA
     }
 
+    // NOTE: Tabs in code blocks are reported without fixes since they can be significant
+    // (e.g. Makefile or TSV)
     #[inline]
     fn check(&self, doc: &Document) -> Result<Vec<Violation>> {
+        let mut code_block_ranges = RangeSet::new();
+        for node in doc.ast.descendants() {
+            if let NodeValue::CodeBlock(_) = node.data.borrow().value {
+                let position = node.data.borrow().sourcepos;
+                code_block_ranges.insert(position.start.line..=position.end.line);
+            }
+        }
+
         let mut violations = vec![];
         for (i, line) in doc.lines.iter().enumerate() {
             let lineno = i + 1;
             if let Some(idx) = line.find('\t') {
                 let position = Sourcepos::from((lineno, idx + 1, lineno, idx + 1));
-                let violation = self.to_violation(doc.path.clone(), position);
+                let mut violation = self.to_violation(doc.path.clone(), position);
+                if !code_block_ranges.contains(&lineno)
+                    && let Some(line_offset) = doc.offset(lineno, 1)
+                {
+                    violation = violation.with_fixes(Self::expand_tabs(line, line_offset));
+                }
                 violations.push(violation);
             }
         }
@@ -75,6 +112,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn check_errors_with_fixes() -> Result<()> {
+        let text = indoc! {"
+            * item
+            \t* nested\twith\ttabs
+        "}
+        .to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let rule = MD010::new();
+        let actual = rule.check(&doc)?;
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((2, 1, 2, 1)))
+                .with_fixes(vec![
+                    Edit::new(7..8, "    ".to_owned()),
+                    Edit::new(16..17, "    ".to_owned()),
+                    Edit::new(21..22, "    ".to_owned()),
+                ]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn check_errors_with_code_block() -> Result<()> {
+        let text = indoc! {"
+            Text\twith a tab
+
+            ```make
+            build:
+            \tcargo build
+            ```
+
+            \tindented\tcode
+        "}
+        .to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let rule = MD010::new();
+        let actual = rule.check(&doc)?;
+        let expected = vec![
+            rule.to_violation(path.clone(), Sourcepos::from((1, 5, 1, 5)))
+                .with_fixes(vec![Edit::new(4..5, "    ".to_owned())]),
+            rule.to_violation(path.clone(), Sourcepos::from((5, 1, 5, 1))),
+            rule.to_violation(path, Sourcepos::from((8, 1, 8, 1))),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
     #[test]
     fn check_no_errors() -> Result<()> {
         let text = indoc! {"
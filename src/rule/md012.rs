@@ -1,7 +1,7 @@
 use comrak::nodes::{NodeValue, Sourcepos};
 use miette::Result;
 
-use crate::{Document, collection::RangeSet, violation::Violation};
+use crate::{Document, collection::RangeSet, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -54,7 +54,11 @@ impl RuleLike for MD012 {
                 && !code_block_ranges.contains(&lineno)
             {
                 let position = Sourcepos::from((lineno, 1, lineno, 1));
-                let violation = self.to_violation(doc.path.clone(), position);
+                let mut violation = self.to_violation(doc.path.clone(), position);
+                if let Some(start) = doc.offset(lineno, 1) {
+                    let end = doc.offset(lineno + 1, 1).unwrap_or(doc.text.len());
+                    violation = violation.with_fixes(vec![Edit::deletion(start..end)]);
+                }
                 violations.push(violation);
             }
 
@@ -89,7 +93,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD012::new();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((3, 1, 3, 1)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((3, 1, 3, 1)))
+                .with_fixes(vec![Edit::deletion(16..17)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -110,7 +117,35 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD012::new();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((5, 1, 5, 1)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((5, 1, 5, 1)))
+                .with_fixes(vec![Edit::deletion(14..15)]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn check_errors_with_fixes() -> Result<()> {
+        let text = indoc! {"
+            Some text here
+
+
+
+            Some more text here
+        "}
+        .to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let rule = MD012::new();
+        let actual = rule.check(&doc)?;
+        let expected = vec![
+            rule.to_violation(path.clone(), Sourcepos::from((3, 1, 3, 1)))
+                .with_fixes(vec![Edit::deletion(16..17)]),
+            rule.to_violation(path, Sourcepos::from((4, 1, 4, 1)))
+                .with_fixes(vec![Edit::deletion(17..18)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -1,7 +1,8 @@
-use comrak::nodes::NodeValue;
+use comrak::nodes::{NodeValue, Sourcepos};
 use miette::Result;
 
 use crate::Document;
+use crate::fix::Edit;
 use crate::violation::Violation;
 
 use super::{Metadata, RuleLike, Tag};
@@ -23,6 +24,17 @@ impl MD018 {
     pub const fn new() -> Self {
         Self {}
     }
+
+    // NOTE: A line with more than 6 hashes cannot be an atx heading even with a space
+    fn edit(doc: &Document, position: Sourcepos, text: &str) -> Option<Edit> {
+        let num_hashes = text.len() - text.trim_start_matches('#').len();
+        if position.start.column != 1 || num_hashes > 6 {
+            return None;
+        }
+
+        let offset = doc.offset(position.start.line, 1)? + num_hashes;
+        Some(Edit::insertion(offset, " ".to_owned()))
+    }
 }
 
 impl RuleLike for MD018 {
@@ -44,7 +56,11 @@ impl RuleLike for MD018 {
                             && text.starts_with('#')
                             && !text.ends_with('#')
                         {
-                            let violation = self.to_violation(doc.path.clone(), position);
+                            let text_position = child_node.data.borrow().sourcepos;
+                            let mut violation = self.to_violation(doc.path.clone(), position);
+                            if let Some(edit) = Self::edit(doc, text_position, text) {
+                                violation = violation.with_fixes(vec![edit]);
+                            }
                             violations.push(violation);
                         }
                     }
@@ -80,13 +96,28 @@ mod tests {
         let rule = MD018::default();
         let actual = rule.check(&doc)?;
         let expected = vec![
-            rule.to_violation(path.clone(), Sourcepos::from((1, 1, 1, 9))),
-            rule.to_violation(path, Sourcepos::from((3, 1, 3, 10))),
+            rule.to_violation(path.clone(), Sourcepos::from((1, 1, 1, 9)))
+                .with_fixes(vec![Edit::insertion(1, " ".to_owned())]),
+            rule.to_violation(path, Sourcepos::from((3, 1, 3, 10)))
+                .with_fixes(vec![Edit::insertion(13, " ".to_owned())]),
         ];
         assert_eq!(actual, expected);
         Ok(())
     }
 
+    #[test]
+    fn check_errors_with_too_many_hashes() -> Result<()> {
+        let text = "#######Header 7".to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let rule = MD018::default();
+        let actual = rule.check(&doc)?;
+        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 15)))];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
     #[test]
     fn check_no_errors() -> Result<()> {
         let text = indoc! {"
@@ -1,7 +1,8 @@
-use comrak::nodes::{NodeHeading, NodeValue};
+use comrak::nodes::{NodeHeading, NodeValue, Sourcepos};
 use miette::Result;
 
 use crate::Document;
+use crate::fix::Edit;
 use crate::violation::Violation;
 
 use super::{Metadata, RuleLike, Tag};
@@ -23,6 +24,23 @@ impl MD019 {
     pub const fn new() -> Self {
         Self {}
     }
+
+    fn edit(
+        doc: &Document,
+        heading_position: Sourcepos,
+        first_position: Sourcepos,
+        level: usize,
+    ) -> Option<Edit> {
+        let lineno = heading_position.start.line;
+        let start = doc.offset(lineno, heading_position.start.column)? + level;
+        let end = doc.offset(lineno, first_position.start.column)?;
+        let spaces = doc.text.get(start..end)?;
+        if spaces.is_empty() || !spaces.chars().all(|c| c == ' ' || c == '\t') {
+            return None;
+        }
+
+        Some(Edit::new(start..end, " ".to_owned()))
+    }
 }
 
 impl RuleLike for MD019 {
@@ -55,7 +73,12 @@ impl RuleLike for MD019 {
                         || (heading_position.end.column
                             > last_position.end.column + expected_offset))
                 {
-                    let violation = self.to_violation(doc.path.clone(), heading_position);
+                    let mut violation = self.to_violation(doc.path.clone(), heading_position);
+                    if let Some(edit) =
+                        Self::edit(doc, heading_position, first_position, *level as usize)
+                    {
+                        violation = violation.with_fixes(vec![edit]);
+                    }
                     violations.push(violation);
                 }
             }
@@ -89,8 +112,10 @@ mod tests {
         let rule = MD019::new();
         let actual = rule.check(&doc)?;
         let expected = vec![
-            rule.to_violation(path.clone(), Sourcepos::from((1, 1, 1, 11))),
-            rule.to_violation(path, Sourcepos::from((3, 1, 3, 12))),
+            rule.to_violation(path.clone(), Sourcepos::from((1, 1, 1, 11)))
+                .with_fixes(vec![Edit::new(1..3, " ".to_owned())]),
+            rule.to_violation(path, Sourcepos::from((3, 1, 3, 12)))
+                .with_fixes(vec![Edit::new(15..17, " ".to_owned())]),
         ];
         assert_eq!(actual, expected);
         Ok(())
@@ -1,7 +1,7 @@
-use comrak::nodes::NodeValue;
+use comrak::nodes::{NodeValue, Sourcepos};
 use miette::Result;
 
-use crate::{Document, violation::Violation};
+use crate::{Document, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -22,6 +22,17 @@ impl MD023 {
     pub const fn new() -> Self {
         Self {}
     }
+
+    fn edit(doc: &Document, position: Sourcepos) -> Option<Edit> {
+        let start = doc.offset(position.start.line, 1)?;
+        let end = doc.offset(position.start.line, position.start.column)?;
+        let indent = doc.text.get(start..end)?;
+        if !indent.chars().all(|c| c == ' ' || c == '\t') {
+            return None;
+        }
+
+        Some(Edit::deletion(start..end))
+    }
 }
 
 impl RuleLike for MD023 {
@@ -38,7 +49,10 @@ impl RuleLike for MD023 {
             if let NodeValue::Heading(_) = node.data.borrow().value {
                 let position = node.data.borrow().sourcepos;
                 if position.start.column > 1 {
-                    let violation = self.to_violation(doc.path.clone(), position);
+                    let mut violation = self.to_violation(doc.path.clone(), position);
+                    if let Some(edit) = Self::edit(doc, position) {
+                        violation = violation.with_fixes(vec![edit]);
+                    }
                     violations.push(violation);
                 }
             }
@@ -71,7 +85,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD023::new();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((3, 3, 3, 19)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((3, 3, 3, 19)))
+                .with_fixes(vec![Edit::deletion(11..13)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
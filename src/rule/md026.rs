@@ -1,7 +1,7 @@
-use comrak::nodes::NodeValue;
+use comrak::nodes::{AstNode, NodeValue};
 use miette::Result;
 
-use crate::{Document, violation::Violation};
+use crate::{Document, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -26,6 +26,24 @@ impl MD026 {
     pub const fn new(punctuation: String) -> Self {
         Self { punctuation }
     }
+
+    fn edit<'a>(&self, doc: &Document, text_node: &'a AstNode<'a>, text: &str) -> Option<Edit> {
+        let trimmed_text = text.trim_end_matches(|c| self.punctuation.contains(c));
+
+        // Keep a header consisting only of punctuation so as not to leave it empty
+        if trimmed_text.trim().is_empty() && text_node.previous_sibling().is_none() {
+            return None;
+        }
+
+        let position = text_node.data.borrow().sourcepos;
+        let end = doc.offset(position.end.line, position.end.column)? + 1;
+        let start = end.checked_sub(text.len() - trimmed_text.len())?;
+        if doc.text.get(start..end) != text.get(trimmed_text.len()..) {
+            return None;
+        }
+
+        Some(Edit::deletion(start..end))
+    }
 }
 
 impl Default for MD026 {
@@ -55,7 +73,10 @@ impl RuleLike for MD026 {
                 && self.punctuation.contains(last_char)
             {
                 let position = node.data.borrow().sourcepos;
-                let violation = self.to_violation(doc.path.clone(), position);
+                let mut violation = self.to_violation(doc.path.clone(), position);
+                if let Some(edit) = self.edit(doc, child, text) {
+                    violation = violation.with_fixes(vec![edit]);
+                }
                 violations.push(violation);
             }
         }
@@ -81,7 +102,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD026::default();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 19)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 1, 1, 19)))
+                .with_fixes(vec![Edit::deletion(18..19)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -94,7 +118,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD026::default();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 41)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 1, 1, 41)))
+                .with_fixes(vec![Edit::deletion(40..41)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -107,7 +134,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD026::default();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 21)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 1, 1, 21)))
+                .with_fixes(vec![Edit::deletion(20..21)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -120,7 +150,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD026::default();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 21)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 1, 1, 21)))
+                .with_fixes(vec![Edit::deletion(20..21)]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
@@ -2,7 +2,7 @@ use comrak::nodes::NodeValue;
 use linkify::LinkFinder;
 use miette::Result;
 
-use crate::{Document, violation::Violation};
+use crate::{Document, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -52,7 +52,17 @@ impl RuleLike for MD034 {
                     position.end = position.start.column_add(link.end() as isize);
                     position.start = position.start.column_add(link.start() as isize);
 
-                    let violation = self.to_violation(doc.path.clone(), position);
+                    let mut violation = self.to_violation(doc.path.clone(), position);
+
+                    // NOTE: Suggest a fix only if the source matches the link as is (e.g. no escapes)
+                    if let Some(start) = doc.offset(position.start.line, position.start.column) {
+                        let end = start + link.as_str().len();
+                        if doc.text.get(start..end) == Some(link.as_str()) {
+                            let content = format!("<{}>", link.as_str());
+                            violation = violation.with_fixes(vec![Edit::new(start..end, content)]);
+                        }
+                    }
+
                     violations.push(violation);
                 }
             }
@@ -79,7 +89,26 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD034::default();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 27, 1, 50)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 27, 1, 50)))
+                .with_fixes(vec![Edit::new(
+                    26..49,
+                    "<http://www.example.com/>".to_owned(),
+                )]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn check_errors_with_escape() -> Result<()> {
+        let text = "See http://www.example.com/foo\\_bar.".to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let rule = MD034::default();
+        let actual = rule.check(&doc)?;
+        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 5, 1, 35)))];
         assert_eq!(actual, expected);
         Ok(())
     }
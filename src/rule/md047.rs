@@ -1,7 +1,7 @@
 use comrak::nodes::Sourcepos;
 use miette::Result;
 
-use crate::{Document, violation::Violation};
+use crate::{Document, fix::Edit, violation::Violation};
 
 use super::{Metadata, RuleLike, Tag};
 
@@ -39,7 +39,10 @@ impl RuleLike for MD047 {
         let lineno = doc.lines.len();
         let end_column = doc.lines.last().unwrap_or(&String::new()).len() + 1;
         let position = Sourcepos::from((lineno, 1, lineno, end_column));
-        let violation = self.to_violation(doc.path.clone(), position);
+        let edit = Edit::insertion(doc.text.len(), "\n".to_owned());
+        let violation = self
+            .to_violation(doc.path.clone(), position)
+            .with_fixes(vec![edit]);
 
         Ok(vec![violation])
     }
@@ -63,7 +66,10 @@ mod tests {
         let doc = Document::new(&arena, path.clone(), text)?;
         let rule = MD047::new();
         let actual = rule.check(&doc)?;
-        let expected = vec![rule.to_violation(path, Sourcepos::from((1, 1, 1, 10)))];
+        let expected = vec![
+            rule.to_violation(path, Sourcepos::from((1, 1, 1, 10)))
+                .with_fixes(vec![Edit::insertion(9, "\n".to_owned())]),
+        ];
         assert_eq!(actual, expected);
         Ok(())
     }
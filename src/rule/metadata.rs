@@ -8,3 +8,26 @@ pub struct Metadata {
     pub tags: &'static [Tag],
     pub aliases: &'static [&'static str],
 }
+
+impl Metadata {
+    /// Reported for suppression comments which disable no violations
+    pub const UNUSED_SUPPRESSION: Self = Self {
+        name: "unused-suppression",
+        description: "Suppression comment does not suppress any violations",
+        tags: &[Tag::Html],
+        aliases: &["unused-suppression"],
+    };
+
+    /// Reported for suppression comments which refer to unknown rules
+    pub const UNKNOWN_SUPPRESSION: Self = Self {
+        name: "unknown-suppression",
+        description: "Suppression comment refers to an unknown rule",
+        tags: &[Tag::Html],
+        aliases: &["unknown-suppression"],
+    };
+
+    /// Metadata of violations reported for suppression comments, which are configured
+    /// like rules but not checked by them
+    pub const SUPPRESSIONS: [&'static Self; 2] =
+        [&Self::UNUSED_SUPPRESSION, &Self::UNKNOWN_SUPPRESSION];
+}
@@ -1,6 +1,13 @@
+pub mod baseline;
+pub mod cache;
+pub mod changes;
+mod formatter;
 mod linter;
+pub mod resolver;
 pub mod runner;
+mod suppression;
 pub mod visitor;
 pub mod walker;
 
+pub use formatter::Formatter;
 pub use linter::Linter;
@@ -0,0 +1,196 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{Context as _, IntoDiagnostic as _, Result};
+use serde::{Deserialize, Serialize};
+
+use super::visitor::normalize_path;
+use crate::Violation;
+
+/// Existing violations that are not reported, recorded by path, rule and the content
+/// of the offending lines so that they are still recognized after the lines move
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    violations: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Entry {
+    path: PathBuf,
+    rule: String,
+    fingerprint: String,
+}
+
+impl Entry {
+    fn new(violation: &Violation, sources: &BTreeMap<PathBuf, String>) -> Self {
+        let source = sources.get(violation.path()).map_or("", String::as_str);
+        Self {
+            path: normalize_path(violation.path()),
+            rule: violation.name().to_owned(),
+            fingerprint: format!("{:016x}", violation.fingerprint(source)),
+        }
+    }
+}
+
+impl Baseline {
+    /// Creates a baseline of `violations`, where `sources` are the contents of their files
+    #[inline]
+    #[must_use]
+    pub fn new(violations: &[Violation], sources: &BTreeMap<PathBuf, String>) -> Self {
+        let mut entries: Vec<Entry> = violations
+            .iter()
+            .map(|violation| Entry::new(violation, sources))
+            .collect();
+        entries.sort();
+        Self {
+            violations: entries,
+        }
+    }
+
+    #[inline]
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&json)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    #[inline]
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).into_diagnostic()?;
+        fs::write(path, format!("{json}\n"))
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Returns the violations not in this baseline. Each recorded violation excuses
+    /// only one violation, so that new copies of the same line are still reported
+    #[inline]
+    #[must_use]
+    pub fn filter(
+        &self,
+        violations: Vec<Violation>,
+        sources: &BTreeMap<PathBuf, String>,
+    ) -> Vec<Violation> {
+        let mut counts: BTreeMap<&Entry, usize> = BTreeMap::new();
+        for entry in &self.violations {
+            *counts.entry(entry).or_default() += 1;
+        }
+
+        violations
+            .into_iter()
+            .filter(|violation| {
+                let entry = Entry::new(violation, sources);
+                match counts.get_mut(&entry) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comrak::nodes::Sourcepos;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+    use xxhash_rust::xxh3::xxh3_64;
+
+    use crate::rule::{MD009, MD026, RuleLike as _};
+
+    use super::*;
+
+    fn violation(path: &str, line: usize) -> Violation {
+        let position = Sourcepos::from((line, 1, line, 8));
+        MD026::default().to_violation(Path::new(path).to_path_buf(), position)
+    }
+
+    #[test]
+    fn filter() {
+        let old_sources = BTreeMap::from([(
+            Path::new("./README.md").to_path_buf(),
+            "# Hello.\n\n# World!\n".to_owned(),
+        )]);
+        let baseline = Baseline::new(
+            &[violation("./README.md", 1), violation("./README.md", 3)],
+            &old_sources,
+        );
+
+        // Lines added above and indentation do not matter, but copies and other rules do
+        let new_sources = BTreeMap::from([(
+            Path::new("README.md").to_path_buf(),
+            "Text\n\n  # Hello.\n\n# World!\n\n# Hello.\n".to_owned(),
+        )]);
+        let other_rule = MD009::default().to_violation(
+            Path::new("README.md").to_path_buf(),
+            Sourcepos::from((3, 1, 3, 10)),
+        );
+        let violations = vec![
+            violation("README.md", 3),
+            violation("README.md", 5),
+            violation("README.md", 7),
+            other_rule.clone(),
+        ];
+        let actual = baseline.filter(violations, &new_sources);
+        let expected = vec![violation("README.md", 7), other_rule];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn filter_changed_line() {
+        let sources = BTreeMap::from([(
+            Path::new("README.md").to_path_buf(),
+            "# Hello.\n".to_owned(),
+        )]);
+        let baseline = Baseline::new(&[violation("README.md", 1)], &sources);
+
+        let new_sources = BTreeMap::from([(
+            Path::new("README.md").to_path_buf(),
+            "# Hello!\n".to_owned(),
+        )]);
+        let violations = vec![violation("README.md", 1)];
+        let actual = baseline.filter(violations.clone(), &new_sources);
+        assert_eq!(actual, violations);
+    }
+
+    #[test]
+    fn write_and_load() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("mado-baseline.json");
+        let sources = BTreeMap::from([(
+            Path::new("./README.md").to_path_buf(),
+            "# Hello.\n".to_owned(),
+        )]);
+        let baseline = Baseline::new(&[violation("./README.md", 1)], &sources);
+        baseline.write(&path)?;
+
+        let json = fs::read_to_string(&path).into_diagnostic()?;
+        let expected = format!(
+            "{{\n  \"violations\": [\n    {{\n      \"path\": \"README.md\",\n      \"rule\": \"MD026\",\n      \"fingerprint\": \"{:016x}\"\n    }}\n  ]\n}}\n",
+            xxh3_64(b"# Hello.")
+        );
+        assert_eq!(json, expected);
+        assert_eq!(Baseline::load(&path)?, baseline);
+        Ok(())
+    }
+
+    #[test]
+    fn load_invalid() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("mado-baseline.json");
+        assert!(Baseline::load(&path).is_err());
+
+        fs::write(&path, "[]").into_diagnostic()?;
+        assert!(Baseline::load(&path).is_err());
+        Ok(())
+    }
+}
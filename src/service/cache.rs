@@ -0,0 +1,384 @@
+extern crate alloc;
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{self, Path, PathBuf};
+use std::process;
+
+use comrak::nodes::Sourcepos;
+use miette::{IntoDiagnostic as _, Result};
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::{Xxh3, xxh3_128};
+
+use super::Linter;
+use crate::config::lint::Lint;
+use crate::fix::Edit;
+use crate::rule::Severity;
+use crate::{Config, Violation};
+
+/// Violations of each file stored on disk, which are reused until the file,
+/// its configuration or the version of mado changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    // The linted file, so that entries of removed files can be pruned
+    path: PathBuf,
+    key: String,
+    violations: Vec<CachedViolation>,
+}
+
+// The part of an entry read to prune it without restoring violations
+#[derive(Deserialize)]
+struct EntryPath {
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedViolation {
+    name: String,
+    position: (usize, usize, usize, usize),
+    fixes: Vec<(usize, usize, String)>,
+    severity: Severity,
+}
+
+impl CachedViolation {
+    fn to_violation(&self, path: &Path, linter: &Linter) -> Option<Violation> {
+        let metadata = linter.metadata(&self.name)?;
+        let fixes = self
+            .fixes
+            .iter()
+            .map(|(start, end, content)| Edit::new(*start..*end, content.clone()))
+            .collect();
+        let violation =
+            Violation::new(path.to_path_buf(), metadata, Sourcepos::from(self.position))
+                .with_fixes(fixes)
+                .with_severity(self.severity);
+        Some(violation)
+    }
+}
+
+impl From<&Violation> for CachedViolation {
+    #[inline]
+    fn from(violation: &Violation) -> Self {
+        let position = violation.position();
+        Self {
+            name: violation.name().to_owned(),
+            position: (
+                position.start.line,
+                position.start.column,
+                position.end.line,
+                position.end.column,
+            ),
+            fixes: violation
+                .fixes()
+                .iter()
+                .map(|edit| {
+                    (
+                        edit.range().start,
+                        edit.range().end,
+                        edit.content().to_owned(),
+                    )
+                })
+                .collect(),
+            severity: violation.severity(),
+        }
+    }
+}
+
+impl Cache {
+    /// Name of the cache directory, which is placed at the root of the project
+    pub const DIR: &str = ".mado_cache";
+
+    // Options of `Lint` affecting which files are linted or how violations are reported,
+    // but not the violations of each file
+    const OUTPUT_KEYS: [&str; 10] = [
+        "respect-ignore",
+        "respect-gitignore",
+        "output-format",
+        "reporters",
+        "quiet",
+        "max-warnings",
+        "fix",
+        "cache",
+        "baseline",
+        "exclude",
+    ];
+
+    /// Returns the cache directory of the project containing `dir`, which is at the
+    /// repository root, or next to the nearest configuration outside repositories
+    #[inline]
+    pub fn find_dir(dir: &Path) -> Result<PathBuf> {
+        let absolute_dir = path::absolute(dir).into_diagnostic()?;
+        for ancestor in absolute_dir.ancestors() {
+            if Config::is_root(ancestor)? {
+                return Ok(ancestor.join(Self::DIR));
+            }
+        }
+
+        let root = Config::find_nearest(&absolute_dir)?
+            .and_then(|config_path| config_path.parent().map(Path::to_path_buf))
+            .unwrap_or(absolute_dir);
+        Ok(root.join(Self::DIR))
+    }
+
+    /// Opens the cache in `dir`, creating it if needed
+    #[inline]
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).into_diagnostic()?;
+
+        // Keep the cache out of version control
+        let gitignore_path = dir.join(".gitignore");
+        if !fs::exists(&gitignore_path).into_diagnostic()? {
+            fs::write(&gitignore_path, "*\n").into_diagnostic()?;
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    /// Removes the cache in `dir` and returns false if there is none
+    #[inline]
+    pub fn clean(dir: &Path) -> Result<bool> {
+        match fs::remove_dir_all(dir) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err).into_diagnostic(),
+        }
+    }
+
+    /// Returns a hash of the options of `lint` affecting violations and the version of mado,
+    /// which is shared by files linted with it
+    #[inline]
+    pub fn config_key(lint: &Lint) -> Result<u128> {
+        let mut value = serde_json::to_value(lint).into_diagnostic()?;
+        if let Some(map) = value.as_object_mut() {
+            // Keep the order of the other options, which the hash depends on
+            for key in Self::OUTPUT_KEYS {
+                map.shift_remove(key);
+            }
+        }
+        let json = serde_json::to_vec(&value).into_diagnostic()?;
+        let mut hasher = Xxh3::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(&json);
+        Ok(hasher.digest128())
+    }
+
+    /// Returns a hash of `text` linted with the configuration of `config_key`
+    #[inline]
+    #[must_use]
+    pub fn key(config_key: u128, text: &str) -> u128 {
+        let mut hasher = Xxh3::new();
+        hasher.update(&config_key.to_le_bytes());
+        hasher.update(text.as_bytes());
+        hasher.digest128()
+    }
+
+    /// Returns the violations of `path` stored with `key`. Violations of rules unknown to
+    /// `linter` are never returned, since they cannot be restored
+    #[inline]
+    #[must_use]
+    pub fn get(&self, path: &Path, key: u128, linter: &Linter) -> Option<Vec<Violation>> {
+        let json = fs::read(self.entry_path(path).ok()?).ok()?;
+        let entry: Entry = serde_json::from_slice(&json).ok()?;
+        // Entries of different paths may have the same name in theory
+        if entry.path != path::absolute(path).ok()? {
+            return None;
+        }
+        if entry.key != format!("{key:032x}") {
+            return None;
+        }
+
+        entry
+            .violations
+            .iter()
+            .map(|violation| violation.to_violation(path, linter))
+            .collect()
+    }
+
+    /// Stores the violations of `path` with `key`, replacing the previous ones
+    #[inline]
+    pub fn set(&self, path: &Path, key: u128, violations: &[Violation]) -> Result<()> {
+        let entry = Entry {
+            path: path::absolute(path).into_diagnostic()?,
+            key: format!("{key:032x}"),
+            violations: violations.iter().map(CachedViolation::from).collect(),
+        };
+        let json = serde_json::to_vec(&entry).into_diagnostic()?;
+
+        // Write to a temporary file and rename it, so that other runs never read
+        // a partially written entry
+        let entry_path = self.entry_path(path)?;
+        let tmp_path = entry_path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&tmp_path, json).into_diagnostic()?;
+        fs::rename(&tmp_path, &entry_path).into_diagnostic()
+    }
+
+    /// Removes entries of files that no longer exist and entries that cannot be read,
+    /// e.g. those written by other versions of mado
+    #[inline]
+    pub fn prune(&self) -> Result<()> {
+        for dir_entry in fs::read_dir(&self.dir).into_diagnostic()? {
+            let entry_path = dir_entry.into_diagnostic()?.path();
+            // Temporary files may be being written by other runs
+            if entry_path.extension() != Some("json".as_ref()) {
+                continue;
+            }
+
+            let is_stale = fs::read(&entry_path)
+                .ok()
+                .and_then(|json| serde_json::from_slice::<EntryPath>(&json).ok())
+                .is_none_or(|entry| !entry.path.is_file());
+            if is_stale {
+                match fs::remove_file(&entry_path) {
+                    Ok(()) => {}
+                    // Removed by another run at the same time
+                    Err(err) if err.kind() == ErrorKind::NotFound => {}
+                    Err(err) => return Err(err).into_diagnostic(),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Each file has a single entry named after its absolute path
+    fn entry_path(&self, path: &Path) -> Result<PathBuf> {
+        let absolute_path = path::absolute(path).into_diagnostic()?;
+        let hash = xxh3_128(absolute_path.as_os_str().as_encoded_bytes());
+        Ok(self.dir.join(format!("{hash:032x}.json")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use globset::Glob;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use crate::Rule;
+    use crate::output::Format;
+    use crate::rule::{MD009, MD026, RuleLike as _};
+
+    use super::*;
+
+    #[test]
+    fn get_and_set() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let cache = Cache::open(&tmp_dir.path().join(Cache::DIR))?;
+        let linter = Linter::new(vec![Rule::MD026(MD026::default())]);
+        let path = Path::new("README.md");
+        let violations = vec![
+            MD026::default()
+                .to_violation(path.to_path_buf(), Sourcepos::from((1, 1, 1, 8)))
+                .with_fixes(vec![Edit::deletion(7..8)])
+                .with_severity(Severity::Warning),
+        ];
+        assert_eq!(cache.get(path, 1, &linter), None);
+
+        cache.set(path, 1, &violations)?;
+        assert_eq!(cache.get(path, 1, &linter), Some(violations));
+        assert_eq!(cache.get(path, 2, &linter), None);
+        assert_eq!(cache.get(Path::new("other.md"), 1, &linter), None);
+
+        // Violations of disabled rules cannot be restored
+        let other_linter = Linter::new(vec![Rule::MD009(MD009::default())]);
+        assert_eq!(cache.get(path, 1, &other_linter), None);
+
+        cache.set(path, 2, &[])?;
+        assert_eq!(cache.get(path, 1, &linter), None);
+        assert_eq!(cache.get(path, 2, &linter), Some(vec![]));
+        Ok(())
+    }
+
+    #[test]
+    fn prune() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let dir = tmp_dir.path().join(Cache::DIR);
+        let cache = Cache::open(&dir)?;
+        let linter = Linter::new(vec![]);
+        let path = tmp_dir.path().join("a.md");
+        let removed_path = tmp_dir.path().join("b.md");
+        fs::write(&path, "").into_diagnostic()?;
+        fs::write(&removed_path, "").into_diagnostic()?;
+        cache.set(&path, 1, &[])?;
+        cache.set(&removed_path, 1, &[])?;
+        fs::write(dir.join("invalid.json"), "{}").into_diagnostic()?;
+
+        fs::remove_file(&removed_path).into_diagnostic()?;
+        cache.prune()?;
+        assert_eq!(cache.get(&path, 1, &linter), Some(vec![]));
+        assert!(!fs::exists(cache.entry_path(&removed_path)?).into_diagnostic()?);
+        assert!(!fs::exists(dir.join("invalid.json")).into_diagnostic()?);
+        assert!(fs::exists(dir.join(".gitignore")).into_diagnostic()?);
+        Ok(())
+    }
+
+    #[test]
+    fn open_and_clean() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let dir = tmp_dir.path().join(Cache::DIR);
+        Cache::open(&dir)?;
+        let gitignore = fs::read_to_string(dir.join(".gitignore")).into_diagnostic()?;
+        assert_eq!(gitignore, "*\n");
+
+        assert!(Cache::clean(&dir)?);
+        assert!(!fs::exists(&dir).into_diagnostic()?);
+        assert!(!Cache::clean(&dir)?);
+        Ok(())
+    }
+
+    #[test]
+    fn find_dir() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let project_dir = tmp_dir.path().join("project");
+        let docs_dir = project_dir.join("docs");
+        fs::create_dir_all(&docs_dir).into_diagnostic()?;
+        fs::write(project_dir.join("mado.toml"), "").into_diagnostic()?;
+        assert_eq!(Cache::find_dir(&docs_dir)?, project_dir.join(Cache::DIR));
+
+        // The repository root takes precedence over nested configurations
+        let repo_dir = tmp_dir.path().join("repo");
+        let package_dir = repo_dir.join("package");
+        fs::create_dir_all(repo_dir.join(".git")).into_diagnostic()?;
+        fs::create_dir_all(&package_dir).into_diagnostic()?;
+        fs::write(package_dir.join("mado.toml"), "").into_diagnostic()?;
+        assert_eq!(Cache::find_dir(&package_dir)?, repo_dir.join(Cache::DIR));
+        Ok(())
+    }
+
+    #[test]
+    fn key() -> Result<()> {
+        let config = Config::default();
+        let config_key = Cache::config_key(&config.lint)?;
+        assert_eq!(Cache::config_key(&config.lint)?, config_key);
+        assert_eq!(Cache::key(config_key, "a"), Cache::key(config_key, "a"));
+        assert_ne!(Cache::key(config_key, "a"), Cache::key(config_key, "b"));
+
+        // Output options do not affect violations
+        let mut output_lint = config.lint.clone();
+        output_lint.output_format = Some(Format::Json);
+        output_lint.reporters = vec![(Format::Json, PathBuf::from("report.json"))];
+        output_lint.quiet = true;
+        output_lint.fix = true;
+        output_lint.cache = false;
+        output_lint.baseline = Some(PathBuf::from("baseline.json"));
+        output_lint.exclude = vec![Glob::new("*.md").into_diagnostic()?];
+        assert_eq!(Cache::config_key(&output_lint)?, config_key);
+
+        let mut lint = config.lint;
+        lint.rules = vec![];
+        let other_config_key = Cache::config_key(&lint)?;
+        assert_ne!(other_config_key, config_key);
+        assert_ne!(
+            Cache::key(other_config_key, "a"),
+            Cache::key(config_key, "a")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::ops::RangeInclusive;
+use std::path::{self, Path, PathBuf};
+use std::process::Command;
+
+use ignore::types::TypesBuilder;
+use miette::{IntoDiagnostic as _, Result, miette};
+
+use super::visitor::normalize_path;
+use crate::Violation;
+
+/// Files and lines changed since a git revision, including uncommitted and untracked files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    // Changed lines of each file relative to the directory, or `None` if the whole file is new
+    files: BTreeMap<PathBuf, Option<Vec<RangeInclusive<usize>>>>,
+}
+
+impl Changes {
+    /// Asks git in `dir` for the changes since `rev`
+    #[inline]
+    pub fn since(dir: &Path, rev: &str) -> Result<Self> {
+        let diff = Self::git(
+            dir,
+            &[
+                "-c",
+                "core.quotepath=off",
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-prefix",
+                "--relative",
+                rev,
+                "--",
+            ],
+        )?;
+        let mut changes = Self::parse(&diff);
+
+        let untracked = Self::git(dir, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+        for path in untracked.split('\0').filter(|path| !path.is_empty()) {
+            changes.files.insert(PathBuf::from(path), None);
+        }
+
+        Ok(changes)
+    }
+
+    /// Returns the changed Markdown files in any of `patterns`
+    #[inline]
+    pub fn files(&self, patterns: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let roots = patterns
+            .iter()
+            .map(path::absolute)
+            .collect::<Result<Vec<_>, _>>()
+            .into_diagnostic()?;
+
+        // Explicitly given files are never filtered by the walker
+        let types = TypesBuilder::new()
+            .add_defaults()
+            .select("markdown")
+            .build()
+            .into_diagnostic()?;
+
+        let mut files = vec![];
+        for path in self.files.keys() {
+            let absolute_path = path::absolute(path).into_diagnostic()?;
+            if types.matched(path, false).is_whitelist()
+                && roots.iter().any(|root| absolute_path.starts_with(root))
+            {
+                files.push(path.clone());
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Returns true if any line of `violation` is changed
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, violation: &Violation) -> bool {
+        let position = violation.position();
+        let lines = position.start.line..=position.end.line.max(position.start.line);
+        match self.files.get(&normalize_path(violation.path())) {
+            Some(Some(ranges)) => ranges
+                .iter()
+                .any(|range| range.start() <= lines.end() && lines.start() <= range.end()),
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .map_err(|err| miette!("Failed to run git. {err}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(miette!("Failed to run git. {}", stderr.trim()));
+        }
+
+        String::from_utf8(output.stdout).into_diagnostic()
+    }
+
+    // Parses a diff without context lines, where each hunk header is like `@@ -1,2 +3,4 @@`
+    fn parse(diff: &str) -> Self {
+        let mut files: BTreeMap<PathBuf, Option<Vec<RangeInclusive<usize>>>> = BTreeMap::new();
+        let mut current = None;
+        for line in diff.lines() {
+            if let Some(header) = line.strip_prefix("+++ ") {
+                // Deleted files have no lines to report
+                let name = Self::parse_path(header);
+                current = (name != "/dev/null").then(|| PathBuf::from(name));
+                if let Some(path) = &current {
+                    files.insert(path.clone(), Some(vec![]));
+                }
+            } else if let Some(header) = line.strip_prefix("@@ ")
+                && let Some(range) = Self::parse_hunk(header)
+                && let Some(Some(ranges)) = current.as_ref().and_then(|path| files.get_mut(path))
+            {
+                ranges.push(range);
+            }
+        }
+
+        Self { files }
+    }
+
+    // Git appends a tab to paths with spaces, and quotes ones with special characters
+    // like `"a\"b.md"`, escaping non-ASCII bytes in octal unless `core.quotepath` is off
+    fn parse_path(header: &str) -> String {
+        let name = header.strip_suffix('\t').unwrap_or(header);
+        let Some(quoted) = name
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+        else {
+            return name.to_owned();
+        };
+
+        let mut bytes = vec![];
+        let mut chars = quoted.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+
+            match chars.next() {
+                Some('a') => bytes.push(0x07),
+                Some('b') => bytes.push(0x08),
+                Some('f') => bytes.push(0x0c),
+                Some('n') => bytes.push(b'\n'),
+                Some('r') => bytes.push(b'\r'),
+                Some('t') => bytes.push(b'\t'),
+                Some('v') => bytes.push(0x0b),
+                Some(digit @ '0'..='7') => {
+                    let octal: String = [Some(digit), chars.next(), chars.next()]
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    bytes.push(u8::from_str_radix(&octal, 8).unwrap_or_default());
+                }
+                Some(other) => {
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                }
+                None => {}
+            }
+        }
+
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn parse_hunk(header: &str) -> Option<RangeInclusive<usize>> {
+        let added = header.split(' ').find_map(|part| part.strip_prefix('+'))?;
+        let (start, count) = match added.split_once(',') {
+            Some((start, count)) => (start.parse().ok()?, count.parse().ok()?),
+            None => (added.parse().ok()?, 1),
+        };
+
+        // Removed lines touch the lines before and after them
+        if count == 0 {
+            return Some(start..=start + 1);
+        }
+
+        Some(start..=start + count - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use comrak::nodes::Sourcepos;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use crate::rule::{MD026, RuleLike as _};
+
+    use super::*;
+
+    fn violation(path: &str, start: usize, end: usize) -> Violation {
+        let position = Sourcepos::from((start, 1, end, 1));
+        MD026::default().to_violation(Path::new(path).to_path_buf(), position)
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> Result<()> {
+        let args = [
+            &["-c", "user.name=mado", "-c", "user.email=mado@example.com"],
+            args,
+        ]
+        .concat();
+        Changes::git(dir, &args).map(|_| ())
+    }
+
+    #[test]
+    fn parse() {
+        let diff = indoc! {"
+            diff --git README.md README.md
+            index 1234567..89abcde 100644
+            --- README.md
+            +++ README.md
+            @@ -2 +2 @@ Title
+            -old
+            +new
+            @@ -10,0 +11,3 @@ Text
+            +a
+            +b
+            +c
+            @@ -20,2 +22,0 @@
+            -d
+            -e
+            diff --git old.md old.md
+            deleted file mode 100644
+            --- old.md
+            +++ /dev/null
+            @@ -1 +0,0 @@
+            -old
+        "};
+        let actual = Changes::parse(diff);
+        let expected = BTreeMap::from([(
+            Path::new("README.md").to_path_buf(),
+            Some(vec![2..=2, 11..=13, 22..=23]),
+        )]);
+        assert_eq!(actual.files, expected);
+    }
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(Changes::parse_path("README.md"), "README.md");
+        assert_eq!(Changes::parse_path("my notes.md\t"), "my notes.md");
+        assert_eq!(Changes::parse_path(r#""a\"b\\c.md""#), r#"a"b\c.md"#);
+        assert_eq!(Changes::parse_path(r#""tab\there.md""#), "tab\there.md");
+        assert_eq!(Changes::parse_path(r#""\343\201\202.md""#), "\u{3042}.md");
+    }
+
+    #[test]
+    fn contains() {
+        let changes = Changes::parse(indoc! {"
+            +++ docs/README.md
+            @@ -5,0 +6,2 @@
+        "});
+        assert!(!changes.contains(&violation("./docs/README.md", 5, 5)));
+        assert!(changes.contains(&violation("./docs/README.md", 6, 6)));
+        assert!(changes.contains(&violation("docs/README.md", 7, 9)));
+        assert!(changes.contains(&violation("docs/README.md", 1, 6)));
+        assert!(!changes.contains(&violation("docs/README.md", 8, 8)));
+        assert!(!changes.contains(&violation("README.md", 6, 6)));
+    }
+
+    #[test]
+    fn since() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let dir = tmp_dir.path();
+        fs::create_dir(dir.join("docs")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Title\n\nText\n").into_diagnostic()?;
+        fs::write(dir.join("docs/index.md"), "# Docs\n").into_diagnostic()?;
+        fs::write(dir.join("my notes.md"), "# Notes\n").into_diagnostic()?;
+        fs::write(dir.join(r#"a"b.md"#), "# Quoted\n").into_diagnostic()?;
+        fs::write(dir.join(".gitignore"), "ignored.md\n").into_diagnostic()?;
+        git(dir, &["init", "--quiet"])?;
+        git(dir, &["add", "."])?;
+        git(dir, &["commit", "--quiet", "-m", "Initial commit"])?;
+
+        fs::write(dir.join("README.md"), "# Title\n\nText\nMore\n").into_diagnostic()?;
+        fs::write(dir.join("my notes.md"), "# Notes\n\nMore\n").into_diagnostic()?;
+        fs::write(dir.join(r#"a"b.md"#), "# Quoted\nMore\n").into_diagnostic()?;
+        fs::write(dir.join("new.md"), "# New\n").into_diagnostic()?;
+        fs::write(dir.join("ignored.md"), "# Ignored\n").into_diagnostic()?;
+        fs::write(dir.join("notes.txt"), "Notes\n").into_diagnostic()?;
+
+        let changes = Changes::since(dir, "HEAD")?;
+        let expected = BTreeMap::from([
+            (Path::new("README.md").to_path_buf(), Some(vec![4..=4])),
+            (Path::new(r#"a"b.md"#).to_path_buf(), Some(vec![2..=2])),
+            (Path::new("my notes.md").to_path_buf(), Some(vec![2..=3])),
+            (Path::new("new.md").to_path_buf(), None),
+            (Path::new("notes.txt").to_path_buf(), None),
+        ]);
+        assert_eq!(changes.files, expected);
+
+        // Paths are relative to the directory
+        let docs_changes = Changes::since(&dir.join("docs"), "HEAD")?;
+        assert_eq!(docs_changes.files, BTreeMap::new());
+
+        assert!(Changes::since(dir, "no-such-rev").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn files() -> Result<()> {
+        let changes = Changes::parse(indoc! {"
+            +++ README.md
+            +++ docs/index.md
+            +++ docs/notes.txt
+        "});
+        let actual = changes.files(&[Path::new(".").to_path_buf()])?;
+        let expected = vec![
+            Path::new("README.md").to_path_buf(),
+            Path::new("docs/index.md").to_path_buf(),
+        ];
+        assert_eq!(actual, expected);
+
+        let docs_files = changes.files(&[Path::new("./docs").to_path_buf()])?;
+        assert_eq!(docs_files, vec![Path::new("docs/index.md").to_path_buf()]);
+        Ok(())
+    }
+}
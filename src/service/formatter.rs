@@ -0,0 +1,1130 @@
+use core::iter;
+use std::path::Path;
+
+use comrak::nodes::{AstNode, ListType, NodeCodeBlock, NodeList, NodeValue, Sourcepos};
+use comrak::{Arena, Options, parse_document};
+use miette::Result;
+use rustc_hash::FxHashMap;
+
+use crate::Document;
+use crate::config::Lint;
+use crate::config::lint::RuleSet;
+use crate::fix::{self, Edit};
+use crate::rule::md003::HeadingStyle;
+use crate::rule::md004::ListStyle;
+use crate::rule::md029::OrderedListStyle;
+use crate::rule::md035::HorizontalRuleStyle;
+use crate::rule::md046::CodeBlockStyle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListMarkerSpacing {
+    ul_single: usize,
+    ol_single: usize,
+    ul_multi: usize,
+    ol_multi: usize,
+}
+
+/// Re-emits documents so that they conform to the configured styles
+///
+/// Only the reformatted constructs are rewritten. A style is applied only if
+/// its rule is enabled in the lint configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Formatter {
+    heading_style: Option<HeadingStyle>,
+    list_style: Option<ListStyle>,
+    ordered_list_style: Option<OrderedListStyle>,
+    list_marker_spacing: Option<ListMarkerSpacing>,
+    hr_style: Option<HorizontalRuleStyle>,
+    code_block_style: Option<CodeBlockStyle>,
+}
+
+impl Formatter {
+    // NOTE: Each pass reparses the document, so that list edits (which shift
+    // indentation) never overlap with block edits (which replace whole lines)
+    const MAX_ITERATIONS: usize = 10;
+
+    const TAB_SIZE: usize = 4;
+
+    #[inline]
+    pub fn format(&self, path: &Path, text: String) -> Result<String> {
+        let mut text = text;
+        for _ in 0..Self::MAX_ITERATIONS {
+            let mut changed = false;
+            let phases: [fn(&Self, &Document) -> Vec<Edit>; 2] =
+                [Self::list_edits, Self::block_edits];
+            for phase in phases {
+                let arena = Arena::new();
+                let doc = Document::new(&arena, path.to_path_buf(), text)?;
+                let edits = phase(self, &doc);
+                match fix::apply(&doc.text, edits) {
+                    Some(formatted) => {
+                        text = formatted;
+                        changed = true;
+                    }
+                    None => text = doc.text,
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(text)
+    }
+
+    fn list_edits(&self, doc: &Document) -> Vec<Edit> {
+        let mut edits = vec![];
+        let mut shifts: FxHashMap<usize, isize> = FxHashMap::default();
+        let mut maybe_first_bullet = None;
+        let mut levels: FxHashMap<usize, u8> = FxHashMap::default();
+
+        for node in doc.ast.descendants() {
+            let NodeValue::Item(item) = node.data.borrow().value else {
+                continue;
+            };
+
+            let level = node
+                .ancestors()
+                .filter(|ancestor| matches!(ancestor.data.borrow().value, NodeValue::List(_)))
+                .count();
+            let bullet_char = if item.list_type == ListType::Bullet {
+                let first_bullet = *maybe_first_bullet.get_or_insert(item.bullet_char);
+                let level_bullet = *levels.entry(level).or_insert(item.bullet_char);
+                match &self.list_style {
+                    Some(ListStyle::Consistent) => first_bullet,
+                    Some(ListStyle::Asterisk) => b'*',
+                    Some(ListStyle::Plus) => b'+',
+                    Some(ListStyle::Dash) => b'-',
+                    Some(ListStyle::Sublist) => level_bullet,
+                    _ => item.bullet_char,
+                }
+            } else {
+                item.bullet_char
+            };
+
+            if let Some((edit, shift)) = self.list_item_edit(doc, node, &item, bullet_char) {
+                if let Some(edit) = edit {
+                    edits.push(edit);
+                }
+
+                if shift != 0 {
+                    let position = node.data.borrow().sourcepos;
+                    for lineno in position.start.line + 1..=Self::end_line(position) {
+                        *shifts.entry(lineno).or_default() += shift;
+                    }
+                }
+            }
+        }
+
+        for (lineno, shift) in shifts {
+            let Some(line) = doc.lines.get(lineno - 1) else {
+                continue;
+            };
+            let Some(offset) = doc.offset(lineno, 1) else {
+                continue;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            let edit = if shift > 0 {
+                Edit::insertion(offset, " ".repeat(shift.unsigned_abs()))
+            } else {
+                Edit::deletion(offset..offset + indent.min(shift.unsigned_abs()))
+            };
+            edits.push(edit);
+        }
+
+        edits
+    }
+
+    /// Returns an edit for the marker of a list item and how far its content shifts
+    #[allow(clippy::cast_possible_wrap)]
+    fn list_item_edit<'a>(
+        &self,
+        doc: &Document,
+        node: &'a AstNode<'a>,
+        item: &NodeList,
+        bullet_char: u8,
+    ) -> Option<(Option<Edit>, isize)> {
+        let position = node.data.borrow().sourcepos;
+        let line = doc.lines.get(position.start.line - 1)?;
+        let marker_start = position.start.column - 1;
+        let rest = line.get(marker_start..)?;
+
+        let (old_marker_len, new_marker) = match item.list_type {
+            ListType::Bullet => (1, (bullet_char as char).to_string()),
+            ListType::Ordered => {
+                let digits = rest.chars().take_while(char::is_ascii_digit).count();
+                let delimiter = rest.get(digits..=digits)?;
+                let number = match (&self.ordered_list_style, node.previous_sibling()) {
+                    (Some(OrderedListStyle::One), _) => 1,
+                    (Some(OrderedListStyle::Ordered), Some(_)) => {
+                        let index = node.preceding_siblings().count() - 1;
+                        Self::list_start(node)? + index
+                    }
+                    _ => item.start,
+                };
+                (digits + 1, format!("{number}{delimiter}"))
+            }
+        };
+
+        let old_marker = rest.get(..old_marker_len)?;
+        let after_marker = rest.get(old_marker_len..)?;
+        let old_spaces = after_marker.len() - after_marker.trim_start_matches(' ').len();
+        let content = after_marker.get(old_spaces..)?;
+
+        // NOTE: Keep the spacing of empty items, items starting with an indented
+        // code block and items using tabs as is
+        let target_spaces = match &self.list_marker_spacing {
+            Some(spacing)
+                if !content.is_empty() && !content.starts_with('\t') && old_spaces <= 4 =>
+            {
+                let is_multi = Self::is_multi(node);
+                Some(match (is_multi, item.list_type) {
+                    (true, ListType::Bullet) => spacing.ul_multi,
+                    (true, ListType::Ordered) => spacing.ol_multi,
+                    (false, ListType::Bullet) => spacing.ul_single,
+                    (false, ListType::Ordered) => spacing.ol_single,
+                })
+            }
+            _ => None,
+        };
+
+        let old_padding = old_marker_len + old_spaces;
+        let mut new_spaces = target_spaces.map_or(old_spaces, |spaces| spaces.max(1));
+        if new_marker.len() + new_spaces != old_padding && !Self::is_shiftable(doc, node) {
+            // Keep the content column where the item content cannot be re-indented safely
+            new_spaces = old_padding.checked_sub(new_marker.len())?;
+            if new_spaces == 0 && !content.is_empty() {
+                return None;
+            }
+        }
+
+        let replacement = format!("{new_marker}{}", " ".repeat(new_spaces));
+        if Self::is_thematic_break(&format!("{replacement}{content}")) {
+            return None;
+        }
+
+        let shift = (new_marker.len() + new_spaces) as isize - old_padding as isize;
+        let edit = (old_marker != new_marker || old_spaces != new_spaces).then(|| {
+            let start = doc.offset(position.start.line, position.start.column)?;
+            Some(Edit::new(start..start + old_padding, replacement))
+        });
+
+        Some((edit.flatten(), shift))
+    }
+
+    fn list_start<'a>(item_node: &'a AstNode<'a>) -> Option<usize> {
+        let first_item = item_node.parent()?.first_child()?;
+        match first_item.data.borrow().value {
+            NodeValue::Item(item) => Some(item.start),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the item has multiple paragraphs or a paragraph with multiple lines
+    fn is_multi<'a>(item_node: &'a AstNode<'a>) -> bool {
+        if item_node.children().count() > 1 {
+            return true;
+        }
+
+        item_node.first_child().is_some_and(|child_node| {
+            child_node.data.borrow().value == NodeValue::Paragraph
+                && child_node
+                    .children()
+                    .any(|inline_node| inline_node.data.borrow().value == NodeValue::SoftBreak)
+        })
+    }
+
+    /// Returns true if the lines of the node can be re-indented by adding or removing leading spaces
+    fn is_shiftable<'a>(doc: &Document, node: &'a AstNode<'a>) -> bool {
+        let in_list = node.ancestors().skip(1).all(|ancestor| {
+            matches!(
+                ancestor.data.borrow().value,
+                NodeValue::Document | NodeValue::List(_) | NodeValue::Item(_)
+            )
+        });
+        if !in_list {
+            return false;
+        }
+
+        let position = node.data.borrow().sourcepos;
+        let end_line = Self::end_line(position).min(doc.lines.len());
+        doc.lines
+            .get(position.start.line..end_line)
+            .unwrap_or_default()
+            .iter()
+            .all(|line| !line.trim_start_matches(' ').starts_with('\t'))
+    }
+
+    fn block_edits(&self, doc: &Document) -> Vec<Edit> {
+        let mut edits = vec![];
+        edits.extend(self.heading_edits(doc));
+        edits.extend(self.hr_edits(doc));
+        edits.extend(self.code_block_edits(doc));
+        edits
+    }
+
+    fn heading_edits(&self, doc: &Document) -> Vec<Edit> {
+        let Some(style) = &self.heading_style else {
+            return vec![];
+        };
+
+        let mut edits = vec![];
+        let mut maybe_first_style = None;
+        for node in doc.ast.descendants() {
+            let NodeValue::Heading(heading) = node.data.borrow().value else {
+                continue;
+            };
+
+            let current_style = (heading.setext, heading.closed);
+            let first_style = *maybe_first_style.get_or_insert(current_style);
+            let (setext, closed) = match style {
+                HeadingStyle::Consistent => first_style,
+                HeadingStyle::Atx => (false, false),
+                HeadingStyle::AtxClosed => (false, true),
+                HeadingStyle::Setext => (true, false),
+                HeadingStyle::SetextWithAtx if heading.level < 3 => (true, false),
+                HeadingStyle::SetextWithAtx => current_style,
+            };
+
+            // NOTE: Setext headings are only available for level 1 and 2
+            if (setext, closed) == current_style || (setext && heading.level > 2) {
+                continue;
+            }
+
+            let position = node.data.borrow().sourcepos;
+            let Some((lines, first_prefix, prefix)) = Self::container_lines(doc, position) else {
+                continue;
+            };
+            let Some(content) = Self::heading_content(&lines, heading.setext) else {
+                continue;
+            };
+
+            // NOTE: Trailing `#`s after a space would be parsed as a closing sequence of ATX headings
+            let unclosed = content.trim_end_matches('#');
+            let content = if !setext
+                && unclosed.len() < content.len()
+                && (unclosed.is_empty() || unclosed.ends_with([' ', '\t']))
+            {
+                format!("{unclosed}\\{}", &content[unclosed.len()..])
+            } else {
+                content
+            };
+
+            let marker = "#".repeat(heading.level.into());
+            let replacement = match (setext, closed, content.is_empty()) {
+                (true, _, true) => continue,
+                (true, _, false) => {
+                    let underline = if heading.level == 1 { "=" } else { "-" };
+                    let width = content.chars().count().max(3);
+                    let newline = Self::newline(doc);
+                    format!("{content}{newline}{}", underline.repeat(width))
+                }
+                (false, _, true) => marker,
+                (false, false, false) => format!("{marker} {content}"),
+                (false, true, false) => format!("{marker} {content} {marker}"),
+            };
+
+            // NOTE: A setext heading directly following another block would be merged into it
+            if setext && !Self::follows_blank_line(doc, node) {
+                continue;
+            }
+
+            // Formatting must never change the rendered text
+            let text = Self::inline_text(node);
+            let is_same_heading = Self::parse_single(&replacement, |parsed_node| {
+                matches!(parsed_node.data.borrow().value, NodeValue::Heading(parsed) if parsed.level == heading.level && parsed.setext == setext)
+                    && Self::inline_text(parsed_node) == text
+            });
+            let replacement = Self::prefix_lines(doc, &replacement, first_prefix, &prefix);
+            if let Some(edit) = Self::line_edit(doc, position, replacement)
+                && is_same_heading
+            {
+                edits.push(edit);
+            }
+        }
+
+        edits
+    }
+
+    fn heading_content(lines: &[&str], setext: bool) -> Option<String> {
+        if setext {
+            // The last line is the underline
+            let content: Vec<_> = lines
+                .get(..lines.len().checked_sub(1)?)?
+                .iter()
+                .map(|line| line.trim())
+                .collect();
+            return Some(content.join(" "));
+        }
+
+        let line = lines.first()?;
+        let content = line.trim().trim_start_matches('#').trim();
+        let unclosed = content.trim_end_matches('#');
+        if unclosed.is_empty() {
+            return Some(String::new());
+        }
+
+        if unclosed.ends_with([' ', '\t']) {
+            return Some(unclosed.trim_end().to_owned());
+        }
+
+        Some(content.to_owned())
+    }
+
+    fn hr_edits(&self, doc: &Document) -> Vec<Edit> {
+        let Some(style) = &self.hr_style else {
+            return vec![];
+        };
+
+        let mut edits = vec![];
+        let mut maybe_first_hr = None;
+        for node in doc.ast.descendants() {
+            if node.data.borrow().value != NodeValue::ThematicBreak {
+                continue;
+            }
+
+            let position = node.data.borrow().sourcepos;
+            let Some((lines, first_prefix, _)) = Self::container_lines(doc, position) else {
+                continue;
+            };
+            let Some(line) = lines.first().copied() else {
+                continue;
+            };
+            let first_hr = *maybe_first_hr.get_or_insert(line);
+            let hr = match style {
+                HorizontalRuleStyle::Consistent => first_hr,
+                HorizontalRuleStyle::Custom(hr) => hr.as_str(),
+            };
+
+            // NOTE: "---" directly following a paragraph would turn it into a setext heading,
+            // and a list marker followed by a rule of the same character would be a rule itself
+            let replacement = format!("{first_prefix}{hr}");
+            if line == hr
+                || !Self::is_thematic_break(hr)
+                || (hr.trim_start().starts_with('-') && !Self::follows_blank_line(doc, node))
+                || (!first_prefix.trim().is_empty() && Self::is_thematic_break(&replacement))
+            {
+                continue;
+            }
+
+            if let Some(edit) = Self::line_edit(doc, position, replacement) {
+                edits.push(edit);
+            }
+        }
+
+        edits
+    }
+
+    fn code_block_edits(&self, doc: &Document) -> Vec<Edit> {
+        let Some(style) = &self.code_block_style else {
+            return vec![];
+        };
+
+        let mut edits = vec![];
+        let mut maybe_first_fenced = None;
+        for node in doc.ast.descendants() {
+            let NodeValue::CodeBlock(code) = &node.data.borrow().value else {
+                continue;
+            };
+
+            let first_fenced = *maybe_first_fenced.get_or_insert(code.fenced);
+            let fenced = match style {
+                CodeBlockStyle::Fenced => true,
+                CodeBlockStyle::Indented => false,
+                CodeBlockStyle::Consistent => first_fenced,
+            };
+
+            if fenced == code.fenced || !Self::is_shiftable(doc, node) {
+                continue;
+            }
+
+            let position = node.data.borrow().sourcepos;
+            let maybe_edit = if fenced {
+                Self::to_fenced(doc, position, code)
+            } else if Self::follows_blank_line(doc, node) {
+                Self::to_indented(doc, position, code)
+            } else {
+                None
+            };
+
+            if let Some(edit) = maybe_edit {
+                edits.push(edit);
+            }
+        }
+
+        edits
+    }
+
+    fn to_fenced(doc: &Document, position: Sourcepos, code: &NodeCodeBlock) -> Option<Edit> {
+        let line = doc.lines.get(position.start.line - 1)?;
+        let indent = line.get(..position.start.column - 1)?;
+        if indent.len() < Self::TAB_SIZE || indent.contains(|c| c != ' ') {
+            return None;
+        }
+
+        let base = indent.get(..indent.len() - Self::TAB_SIZE)?;
+        let longest_fence = code
+            .literal
+            .lines()
+            .map(|code_line| {
+                let code_line = code_line.trim_start();
+                code_line.len() - code_line.trim_start_matches('`').len()
+            })
+            .max()
+            .unwrap_or_default();
+        let fence = "`".repeat(longest_fence.max(2) + 1);
+        let body: Vec<_> = code
+            .literal
+            .lines()
+            .map(|code_line| Self::indent_line(code_line, base))
+            .collect();
+        let newline = Self::newline(doc);
+        let replacement = format!(
+            "{base}{fence}{newline}{}{newline}{base}{fence}",
+            body.join(newline)
+        );
+
+        let end_line = position.start.line + code.literal.lines().count() - 1;
+        let position = Sourcepos::from((position.start.line, 1, end_line, 1));
+        Self::line_edit(doc, position, replacement)
+    }
+
+    fn to_indented(doc: &Document, position: Sourcepos, code: &NodeCodeBlock) -> Option<Edit> {
+        // NOTE: Indented code blocks cannot have info strings nor leading or trailing blank lines
+        let first_line = code.literal.lines().next()?;
+        let last_line = code.literal.lines().last()?;
+        if !code.info.is_empty()
+            || !code.closed
+            || first_line.trim().is_empty()
+            || last_line.trim().is_empty()
+        {
+            return None;
+        }
+
+        let line = doc.lines.get(position.start.line - 1)?;
+        let base = line.get(..(position.start.column - 1).checked_sub(code.fence_offset)?)?;
+        if base.contains(|c| c != ' ') {
+            return None;
+        }
+
+        let indent = format!("{base}{}", " ".repeat(Self::TAB_SIZE));
+        let body: Vec<_> = code
+            .literal
+            .lines()
+            .map(|code_line| Self::indent_line(code_line, &indent))
+            .collect();
+        Self::line_edit(doc, position, body.join(Self::newline(doc)))
+    }
+
+    fn newline(doc: &Document) -> &'static str {
+        if doc.text.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    }
+
+    fn indent_line(line: &str, indent: &str) -> String {
+        if line.is_empty() {
+            String::new()
+        } else {
+            format!("{indent}{line}")
+        }
+    }
+
+    /// Returns the lines of the given position without the prefixes of their containers,
+    /// such as `>` of block quotes and the indentation of list items, together with the
+    /// prefixes of the first line and the following lines. Returns `None` if a following
+    /// line lacks the prefix, e.g. a lazy continuation line of a block quote
+    fn container_lines<'d>(
+        doc: &'d Document,
+        position: Sourcepos,
+    ) -> Option<(Vec<&'d str>, &'d str, String)> {
+        let first_line = doc.lines.get(position.start.line - 1)?;
+        let first_prefix = first_line.get(..position.start.column - 1)?;
+        // Following lines keep the markers of block quotes, while list markers become spaces
+        let prefix: String = first_prefix
+            .chars()
+            .map(|c| {
+                if c == '>' || c.is_whitespace() {
+                    c
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+
+        let mut lines = vec![first_line.get(first_prefix.len()..)?];
+        for line in doc
+            .lines
+            .get(position.start.line..Self::end_line(position))?
+        {
+            lines.push(line.strip_prefix(prefix.as_str())?);
+        }
+
+        Some((lines, first_prefix, prefix))
+    }
+
+    /// Prepends the prefixes returned by [`Formatter::container_lines`] to each line of `text`
+    fn prefix_lines(doc: &Document, text: &str, first_prefix: &str, prefix: &str) -> String {
+        let newline = Self::newline(doc);
+        let lines: Vec<_> = iter::once(first_prefix)
+            .chain(iter::repeat(prefix))
+            .zip(text.split(newline))
+            .map(|(line_prefix, line)| format!("{line_prefix}{line}"))
+            .collect();
+        lines.join(newline)
+    }
+
+    /// Returns an edit replacing the whole lines of the given position
+    fn line_edit(doc: &Document, position: Sourcepos, replacement: String) -> Option<Edit> {
+        let start = doc.offset(position.start.line, 1)?;
+        let end_line = Self::end_line(position);
+        let end = doc.offset(end_line, 1)? + doc.lines.get(end_line - 1)?.len();
+        Some(Edit::new(start..end, replacement))
+    }
+
+    // NOTE: comrak may report the end of a block as the column 0 of the next line
+    const fn end_line(position: Sourcepos) -> usize {
+        if position.end.column == 0 {
+            position.end.line - 1
+        } else {
+            position.end.line
+        }
+    }
+
+    fn follows_blank_line<'a>(doc: &Document, node: &'a AstNode<'a>) -> bool {
+        // The first block of a container never continues a paragraph
+        let position = node.data.borrow().sourcepos;
+        if node.previous_sibling().is_none() {
+            return true;
+        }
+
+        // Blank lines in block quotes consist of the markers only
+        position.start.line >= 2
+            && doc.lines.get(position.start.line - 2).is_some_and(|line| {
+                line.trim_start_matches(|c: char| c == '>' || c.is_whitespace())
+                    .is_empty()
+            })
+    }
+
+    fn is_thematic_break(line: &str) -> bool {
+        let chars: Vec<_> = line.chars().filter(|c| !c.is_whitespace()).collect();
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        indent < Self::TAB_SIZE
+            && chars.len() >= 3
+            && ['-', '*', '_']
+                .iter()
+                .any(|marker| chars.iter().all(|c| c == marker))
+    }
+
+    /// Returns the text of inline nodes, where line breaks within a paragraph are spaces
+    fn inline_text<'a>(node: &'a AstNode<'a>) -> String {
+        let mut text = String::new();
+        for descendant in node.descendants() {
+            match &descendant.data.borrow().value {
+                NodeValue::Text(literal) => text.push_str(literal),
+                NodeValue::Code(code) => text.push_str(&code.literal),
+                NodeValue::HtmlInline(html) => text.push_str(html),
+                NodeValue::SoftBreak => text.push(' '),
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Returns true if `text` is parsed as a single block satisfying `predicate`
+    fn parse_single<F>(text: &str, predicate: F) -> bool
+    where
+        F: for<'a> Fn(&'a AstNode<'a>) -> bool,
+    {
+        let arena = Arena::new();
+        let ast = parse_document(&arena, text, &Options::default());
+        let mut children = ast.children();
+        let is_satisfied = children.next().is_some_and(predicate);
+        is_satisfied && children.next().is_none()
+    }
+}
+
+impl From<&Lint> for Formatter {
+    #[inline]
+    fn from(config: &Lint) -> Self {
+        let rules = config.flatten_rules();
+        let enabled = |rule: RuleSet| rules.contains(&rule);
+
+        Self {
+            heading_style: enabled(RuleSet::MD003).then(|| config.md003.style.clone()),
+            list_style: enabled(RuleSet::MD004).then(|| config.md004.style.clone()),
+            ordered_list_style: enabled(RuleSet::MD029).then(|| config.md029.style.clone()),
+            list_marker_spacing: enabled(RuleSet::MD030).then_some(ListMarkerSpacing {
+                ul_single: config.md030.ul_single,
+                ol_single: config.md030.ol_single,
+                ul_multi: config.md030.ul_multi,
+                ol_multi: config.md030.ol_multi,
+            }),
+            hr_style: enabled(RuleSet::MD035).then(|| config.md035.style.clone()),
+            code_block_style: enabled(RuleSet::MD046).then(|| config.md046.style.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::config::lint::{MD003, MD004, MD029, MD030, MD035, MD046};
+
+    use super::*;
+
+    fn format(config: &Lint, text: &str) -> Result<String> {
+        let formatter = Formatter::from(config);
+        formatter.format(Path::new("test.md"), text.to_owned())
+    }
+
+    #[test]
+    fn format_headings_to_atx() -> Result<()> {
+        let config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::Atx,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            Heading 1
+            =========
+
+            Heading 2
+            with multiple lines
+            -------------------
+
+            ### Heading 3 ###
+        "};
+        let expected = indoc! {"
+            # Heading 1
+
+            ## Heading 2 with multiple lines
+
+            ### Heading 3
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_headings_to_atx_with_trailing_hashes() -> Result<()> {
+        let text = indoc! {"
+            Learn C #
+            =========
+
+            Learn C#
+            --------
+
+            Learn C ##
+            ----------
+        "};
+        let atx_config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::Atx,
+            },
+            ..Lint::default()
+        };
+        let atx_expected = indoc! {"
+            # Learn C \\#
+
+            ## Learn C#
+
+            ## Learn C \\##
+        "};
+        assert_eq!(format(&atx_config, text)?, atx_expected);
+
+        let atx_closed_config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::AtxClosed,
+            },
+            ..Lint::default()
+        };
+        let atx_closed_expected = indoc! {"
+            # Learn C \\# #
+
+            ## Learn C# ##
+
+            ## Learn C \\## ##
+        "};
+        assert_eq!(format(&atx_closed_config, text)?, atx_closed_expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_headings_to_setext() -> Result<()> {
+        let config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::Setext,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            # Heading 1 #
+
+            ## Heading 2
+            ### Heading 3
+
+            Text
+            # - Not a paragraph
+        "};
+        let expected = indoc! {"
+            Heading 1
+            =========
+
+            Heading 2
+            ---------
+            ### Heading 3
+
+            Text
+            # - Not a paragraph
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_headings_in_containers() -> Result<()> {
+        let text = indoc! {"
+            > # Heading 1
+            >
+            > > ## Heading 2
+
+            - ## Heading 2
+
+              Text
+
+              # Heading 1
+        "};
+        let setext_config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::Setext,
+            },
+            ..Lint::default()
+        };
+        let setext_expected = indoc! {"
+            > Heading 1
+            > =========
+            >
+            > > Heading 2
+            > > ---------
+
+            - Heading 2
+              ---------
+
+              Text
+
+              Heading 1
+              =========
+        "};
+        assert_eq!(format(&setext_config, text)?, setext_expected);
+
+        let atx_closed_config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::AtxClosed,
+            },
+            ..Lint::default()
+        };
+        let atx_closed_expected = indoc! {"
+            > # Heading 1 #
+            >
+            > > ## Heading 2 ##
+
+            - ## Heading 2 ##
+
+              Text
+
+              # Heading 1 #
+        "};
+        assert_eq!(
+            format(&atx_closed_config, setext_expected)?,
+            atx_closed_expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_headings_consistent() -> Result<()> {
+        let text = indoc! {"
+            # Heading 1
+
+            Heading 2
+            ---------
+        "};
+        let expected = indoc! {"
+            # Heading 1
+
+            ## Heading 2
+        "};
+        assert_eq!(format(&Lint::default(), text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_lists() -> Result<()> {
+        let config = Lint {
+            md004: MD004 {
+                style: ListStyle::Dash,
+            },
+            md029: MD029 {
+                style: OrderedListStyle::Ordered,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            *   Item
+                continued
+                + Nested
+
+                  Nested paragraph
+
+            8.  One
+            8.  Two
+            8.  Three
+                continued
+        "};
+        let expected = indoc! {"
+            - Item
+              continued
+              - Nested
+
+                Nested paragraph
+
+            8. One
+            9. Two
+            10. Three
+                continued
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_lists_with_marker_spacing() -> Result<()> {
+        let config = Lint {
+            md029: MD029 {
+                style: OrderedListStyle::One,
+            },
+            md030: MD030 {
+                ul_single: 3,
+                ol_single: 2,
+                ul_multi: 1,
+                ol_multi: 1,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            - Item
+            - Item
+
+            1. One
+            2. Two
+        "};
+        let expected = indoc! {"
+            -   Item
+            -   Item
+
+            1.  One
+            1.  Two
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_lists_in_blockquote() -> Result<()> {
+        let config = Lint {
+            md030: MD030 {
+                ul_single: 3,
+                ol_single: 1,
+                ul_multi: 3,
+                ol_multi: 1,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            > - Item
+            >   continued
+        "};
+        assert_eq!(format(&config, text)?, text);
+        Ok(())
+    }
+
+    #[test]
+    fn format_horizontal_rules() -> Result<()> {
+        let config = Lint {
+            md035: MD035 {
+                style: HorizontalRuleStyle::Custom("---".to_owned()),
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            Text
+
+            ***
+
+            Text
+            ___
+        "};
+        let expected = indoc! {"
+            Text
+
+            ---
+
+            Text
+            ___
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_horizontal_rules_in_containers() -> Result<()> {
+        let config = Lint {
+            md035: MD035 {
+                style: HorizontalRuleStyle::Custom("---".to_owned()),
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            > Text
+            >
+            > ***
+
+            - Text
+
+              * * *
+            - ***
+        "};
+        let expected = indoc! {"
+            > Text
+            >
+            > ---
+
+            - Text
+
+              ---
+            - ***
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_code_blocks_to_fenced() -> Result<()> {
+        let text = indoc! {"
+            Text
+
+                Code
+
+                ```
+                More code
+
+            - Item
+
+                  Code in item
+        "};
+        let expected = indoc! {"
+            Text
+
+            ````
+            Code
+
+            ```
+            More code
+            ````
+
+            - Item
+
+              ```
+              Code in item
+              ```
+        "};
+        assert_eq!(format(&Lint::default(), text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_code_blocks_to_indented() -> Result<()> {
+        let config = Lint {
+            md046: MD046 {
+                style: CodeBlockStyle::Indented,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            Text
+
+            ```
+            Code
+            ```
+
+            ```rust
+            let code_with_info = true;
+            ```
+
+            Text
+            ```
+            Code after a paragraph
+            ```
+        "};
+        let expected = indoc! {"
+            Text
+
+                Code
+
+            ```rust
+            let code_with_info = true;
+            ```
+
+            Text
+            ```
+            Code after a paragraph
+            ```
+        "};
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_crlf() -> Result<()> {
+        let config = Lint {
+            md003: MD003 {
+                style: HeadingStyle::Atx,
+            },
+            ..Lint::default()
+        };
+        let text = "Heading\r\n=======\r\n\r\n    Code\r\n";
+        let expected = "# Heading\r\n\r\n```\r\nCode\r\n```\r\n";
+        assert_eq!(format(&config, text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_disabled_rules() -> Result<()> {
+        let config = Lint {
+            rules: vec![],
+            md003: MD003 {
+                style: HeadingStyle::Atx,
+            },
+            ..Lint::default()
+        };
+        let text = indoc! {"
+            Heading
+            =======
+
+                Code
+        "};
+        assert_eq!(format(&config, text)?, text);
+        Ok(())
+    }
+}
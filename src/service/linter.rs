@@ -1,30 +1,108 @@
-use miette::Result;
+extern crate alloc;
 
+use alloc::collections::BTreeMap;
+use std::path::Path;
+
+use comrak::Arena;
+use miette::{Report, Result};
+
+use super::suppression::Suppressions;
 use crate::Document;
 use crate::Rule;
 use crate::config::Config;
+use crate::fix::{self, Edit};
+use crate::rule::{Metadata, Severity};
 use crate::violation::Violation;
 
 #[derive(Default)]
 pub struct Linter {
     rules: Vec<Rule>,
+    severities: BTreeMap<&'static str, Severity>,
+    report_unused_suppressions: bool,
 }
 
 impl Linter {
+    // NOTE: Fixing one violation may introduce or reveal another one (e.g. removing
+    // trailing spaces can leave consecutive blank lines), so fixes are applied repeatedly
+    const MAX_FIX_ITERATIONS: usize = 10;
+
     #[inline]
     #[must_use]
     pub const fn new(rules: Vec<Rule>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            severities: BTreeMap::new(),
+            report_unused_suppressions: false,
+        }
     }
 
     #[inline]
     pub fn check(&self, doc: &Document) -> Result<Vec<Violation>> {
+        let suppressions = Suppressions::new(doc);
+
         // Iterate rules while unrolling Vec<Result<Vec<..>>> to Result<Vec<..>>
-        self.rules.iter().try_fold(vec![], |mut unrolled, rule| {
+        let mut violations = self.rules.iter().try_fold(vec![], |mut unrolled, rule| {
             let result = rule.check(doc);
-            unrolled.extend(result?);
-            Ok(unrolled)
-        })
+            unrolled.extend(result?.into_iter().filter(|violation| {
+                !suppressions.suppress(violation.name(), violation.position().start.line)
+            }));
+            Ok::<_, Report>(unrolled)
+        })?;
+
+        if self.report_unused_suppressions {
+            violations.extend(suppressions.unused(&doc.path));
+        }
+
+        Ok(violations
+            .into_iter()
+            .map(|violation| {
+                let severity = self.severities.get(violation.name()).copied();
+                violation.with_severity(severity.unwrap_or_default())
+            })
+            .collect())
+    }
+
+    /// Returns the edits attached to the violations in `doc`, which are applied by `fix`
+    #[inline]
+    pub fn edits(&self, doc: &Document) -> Result<Vec<Edit>> {
+        let violations = self.check(doc)?;
+        Ok(violations
+            .iter()
+            .flat_map(Violation::fixes)
+            .cloned()
+            .collect())
+    }
+
+    /// Returns the metadata of violations reported as `name` by this linter
+    #[inline]
+    #[must_use]
+    pub fn metadata(&self, name: &str) -> Option<&'static Metadata> {
+        self.rules
+            .iter()
+            .map(Rule::metadata)
+            .find(|metadata| metadata.name == name)
+            .or_else(|| {
+                Metadata::SUPPRESSIONS
+                    .into_iter()
+                    .find(|metadata| metadata.name == name)
+            })
+    }
+
+    /// Returns `text` with all fixable violations resolved
+    #[inline]
+    pub fn fix(&self, path: &Path, text: String) -> Result<String> {
+        let mut text = text;
+        for _ in 0..Self::MAX_FIX_ITERATIONS {
+            let arena = Arena::new();
+            let doc = Document::new(&arena, path.to_path_buf(), text)?;
+            let edits = self.edits(&doc)?;
+            match fix::apply(&doc.text, edits) {
+                Some(fixed) => text = fixed,
+                None => return Ok(doc.text),
+            }
+        }
+
+        Ok(text)
     }
 }
 
@@ -33,12 +111,17 @@ impl From<&Config> for Linter {
     fn from(config: &Config) -> Self {
         let rules = Vec::from(&config.lint);
 
-        Self { rules }
+        Self {
+            rules,
+            severities: config.lint.rule_severities(),
+            report_unused_suppressions: config.lint.report_unused_suppressions,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::collections::BTreeSet;
     use std::path::Path;
 
     use comrak::{Arena, nodes::Sourcepos};
@@ -46,8 +129,8 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use crate::config::lint::RuleSet;
-    use crate::rule::MD026;
     use crate::rule::RuleLike as _;
+    use crate::rule::{MD009, MD010, MD012, MD018, MD019, MD026, MD047};
 
     use super::*;
 
@@ -69,11 +152,155 @@ mod tests {
         let rules = vec![Rule::MD026(md026.clone())];
         let linter = Linter::new(rules);
         let actual = linter.check(&doc)?;
-        let expected = vec![md026.to_violation(path, Sourcepos::from((6, 1, 6, 19)))];
+        let expected = vec![
+            md026
+                .to_violation(path, Sourcepos::from((6, 1, 6, 19)))
+                .with_fixes(vec![Edit::deletion(66..67)]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn check_with_suppressions() -> Result<()> {
+        let text = indoc! {"
+            # Header.
+
+            <!-- markdownlint-disable no-trailing-punctuation -->
+            # Header.
+            <!-- markdownlint-enable MD026 -->
+
+            <!-- mado-disable-next-line MD026 -->
+            # Header.
+            # Header.
+        "}
+        .to_owned();
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text)?;
+        let md026 = MD026::default();
+        let linter = Linter::new(vec![Rule::MD026(md026.clone())]);
+        let actual = linter.check(&doc)?;
+        let expected = vec![
+            md026
+                .to_violation(path.clone(), Sourcepos::from((1, 1, 1, 9)))
+                .with_fixes(vec![Edit::deletion(8..9)]),
+            md026
+                .to_violation(path, Sourcepos::from((9, 1, 9, 9)))
+                .with_fixes(vec![Edit::deletion(167..168)]),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn fix() -> Result<()> {
+        let text = "# Heading \t\n\n\n\nText  ".to_owned();
+        let path = Path::new("test.md");
+        let rules = vec![
+            Rule::MD009(MD009::new()),
+            Rule::MD010(MD010::new()),
+            Rule::MD012(MD012::new()),
+            Rule::MD047(MD047::new()),
+        ];
+        let linter = Linter::new(rules);
+        let actual = linter.fix(path, text)?;
+        let expected = "# Heading\n\nText\n";
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn fix_with_suppressions() -> Result<()> {
+        let text = "Text  \n<!-- markdownlint-disable-next-line -->\nText  \n".to_owned();
+        let path = Path::new("test.md");
+        let linter = Linter::new(vec![Rule::MD009(MD009::new())]);
+        let actual = linter.fix(path, text)?;
+        let expected = "Text\n<!-- markdownlint-disable-next-line -->\nText  \n";
         assert_eq!(actual, expected);
         Ok(())
     }
 
+    #[test]
+    fn fix_headings() -> Result<()> {
+        let text = "#Heading\n\n##  Heading.\n".to_owned();
+        let path = Path::new("test.md");
+        let rules = vec![
+            Rule::MD018(MD018::new()),
+            Rule::MD019(MD019::new()),
+            Rule::MD026(MD026::default()),
+        ];
+        let linter = Linter::new(rules);
+        let actual = linter.fix(path, text)?;
+        let expected = "# Heading\n\n## Heading\n";
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn fix_no_edits() -> Result<()> {
+        let text = "# Heading\n".to_owned();
+        let path = Path::new("test.md");
+        let linter = Linter::new(vec![Rule::MD018(MD018::new())]);
+        let actual = linter.fix(path, text.clone())?;
+        assert_eq!(actual, text);
+        Ok(())
+    }
+
+    // Fixes attached to violations of any rule must be applied by `fix`
+    #[test]
+    fn fix_all_fixable_rules() -> Result<()> {
+        let texts = [
+            "Text  \n",
+            "Text\twith tab\n",
+            "Text\n\n\nText\n",
+            "#Heading\n",
+            "##  Heading\n",
+            "  # Heading\n",
+            "# Heading.\n",
+            "See http://example.com.\n",
+            "Text",
+        ];
+        let path = Path::new("test.md");
+        let mut fixable_rules = BTreeSet::new();
+        for rule in Vec::from(&Config::default().lint) {
+            let name = rule.metadata().name;
+            let linter = Linter::new(vec![rule]);
+            for text in texts {
+                let arena = Arena::new();
+                let doc = Document::new(&arena, path.to_path_buf(), text.to_owned())?;
+                if linter.edits(&doc)?.is_empty() {
+                    continue;
+                }
+
+                fixable_rules.insert(name);
+                let fixed = linter.fix(path, text.to_owned())?;
+                let fixed_arena = Arena::new();
+                let fixed_doc = Document::new(&fixed_arena, path.to_path_buf(), fixed)?;
+                assert_eq!(linter.check(&fixed_doc)?, vec![], "{name} on {text:?}");
+            }
+        }
+
+        let expected = BTreeSet::from([
+            "MD009", "MD010", "MD012", "MD018", "MD019", "MD023", "MD026", "MD034", "MD047",
+        ]);
+        assert_eq!(fixable_rules, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn metadata() {
+        let linter = Linter::new(vec![Rule::MD026(MD026::default())]);
+        assert_eq!(linter.metadata("MD026"), Some(MD026::default().metadata()));
+        assert_eq!(
+            linter
+                .metadata("unused-suppression")
+                .map(|metadata| metadata.name),
+            Some("unused-suppression")
+        );
+        assert_eq!(linter.metadata("MD001"), None);
+    }
+
     #[test]
     fn from_config() {
         let md026 = MD026::default();
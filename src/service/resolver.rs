@@ -0,0 +1,383 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use std::env;
+use std::path::{self, Path, PathBuf};
+use std::sync::RwLock;
+
+use globset::GlobSet;
+use miette::{IntoDiagnostic as _, Result, miette};
+use rustc_hash::FxHashMap;
+
+use super::visitor::normalize_path;
+use crate::Config;
+
+/// Configuration together with the location it was loaded from
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ResolvedConfig {
+    /// Directory containing the configuration file, if discovered from a linted file
+    pub root: Option<PathBuf>,
+    pub config: Config,
+    pub overrides: Vec<GlobSet>,
+    pub exclusion: GlobSet,
+    /// Warnings about options of the configuration file that are not enforced
+    pub warnings: Vec<String>,
+    /// Files extended by the configuration file, which affect it as well
+    pub bases: Vec<PathBuf>,
+}
+
+impl ResolvedConfig {
+    #[inline]
+    pub fn new(root: Option<PathBuf>, config: Config) -> Result<Self> {
+        let overrides = config.lint.override_sets()?;
+        let exclusion = config.lint.exclude_set()?;
+        Ok(Self {
+            root,
+            config,
+            overrides,
+            exclusion,
+            warnings: vec![],
+            bases: vec![],
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_warnings(self, warnings: Vec<String>) -> Self {
+        Self { warnings, ..self }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_bases(self, bases: Vec<PathBuf>) -> Self {
+        Self { bases, ..self }
+    }
+
+    /// Returns the indices of overrides matching `path`, relative to the configuration file
+    #[inline]
+    pub fn matched_overrides(&self, path: &Path) -> Result<Vec<usize>> {
+        let candidate = self.relative_path(path)?;
+        Ok(self
+            .overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, set)| set.is_match(&candidate))
+            .map(|(index, _)| index)
+            .collect())
+    }
+
+    /// Returns true if `path` is excluded, relative to the configuration file
+    #[inline]
+    pub fn is_excluded(&self, path: &Path) -> Result<bool> {
+        Ok(self.exclusion.is_match(self.relative_path(path)?))
+    }
+
+    fn relative_path(&self, path: &Path) -> Result<PathBuf> {
+        match &self.root {
+            Some(root) => {
+                let absolute_path = path::absolute(path).into_diagnostic()?;
+                Ok(absolute_path
+                    .strip_prefix(root)
+                    .map_or_else(|_| absolute_path.clone(), Path::to_path_buf))
+            }
+            // Patterns of the configuration for the whole run are relative to the current
+            // directory, while absolute paths are given e.g. by editors
+            None if path.is_absolute() => {
+                let current_dir = env::current_dir().into_diagnostic()?;
+                Ok(normalize_path(
+                    path.strip_prefix(&current_dir).unwrap_or(path),
+                ))
+            }
+            None => Ok(normalize_path(path)),
+        }
+    }
+}
+
+/// Resolves the nearest configuration for each linted file, caching results per directory
+#[derive(Debug)]
+pub struct ConfigResolver {
+    base: Arc<ResolvedConfig>,
+    fallback: Option<Arc<ResolvedConfig>>,
+    cache: RwLock<FxHashMap<PathBuf, Arc<ResolvedConfig>>>,
+}
+
+impl ConfigResolver {
+    /// Creates a resolver returning `config` for every file
+    #[inline]
+    pub fn new(config: Config) -> Result<Self> {
+        Ok(Self {
+            base: Arc::new(ResolvedConfig::new(None, config)?),
+            fallback: None,
+            cache: RwLock::new(FxHashMap::default()),
+        })
+    }
+
+    /// Creates a resolver looking up `mado.toml` or `.mado.toml` from each file.
+    /// `config` is the one used for the whole run (e.g. exclusions) and its
+    /// `report-unused-suppressions` is also applied to discovered configurations
+    #[inline]
+    pub fn discover(config: Config) -> Result<Self> {
+        let (global_config, warnings) = Config::resolve_global()?;
+        let fallback =
+            ResolvedConfig::new(None, Self::patch(&config, global_config))?.with_warnings(warnings);
+        Ok(Self {
+            base: Arc::new(ResolvedConfig::new(None, config)?),
+            fallback: Some(Arc::new(fallback)),
+            cache: RwLock::new(FxHashMap::default()),
+        })
+    }
+
+    /// Forgets discovered configurations, so that they are loaded again after changes
+    #[inline]
+    pub fn clear(&self) -> Result<()> {
+        self.cache
+            .write()
+            .map_err(|_err| miette!("Failed to acquire lock"))?
+            .clear();
+        Ok(())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn config(&self) -> &Config {
+        &self.base.config
+    }
+
+    /// Returns true if `path` is excluded by the configuration for the whole run
+    /// or by its nearest one
+    #[inline]
+    pub fn is_excluded(&self, path: &Path) -> Result<bool> {
+        Ok(self.base.is_excluded(path)? || self.resolve(path)?.is_excluded(path)?)
+    }
+
+    #[inline]
+    pub fn resolve(&self, path: &Path) -> Result<Arc<ResolvedConfig>> {
+        let Some(fallback) = &self.fallback else {
+            return Ok(Arc::clone(&self.base));
+        };
+
+        let absolute_path = path::absolute(path).into_diagnostic()?;
+        let Some(dir) = absolute_path.parent() else {
+            return Ok(Arc::clone(fallback));
+        };
+
+        self.resolve_dir(dir, fallback)
+    }
+
+    fn resolve_dir(
+        &self,
+        dir: &Path,
+        fallback: &Arc<ResolvedConfig>,
+    ) -> Result<Arc<ResolvedConfig>> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|_err| miette!("Failed to acquire lock"))?;
+        if let Some(resolved) = cache.get(dir) {
+            return Ok(Arc::clone(resolved));
+        }
+        drop(cache);
+
+        let resolved = if let Some(path) = Config::find(dir)? {
+            let (config, warnings) = Config::load(&path)?;
+            let config = Self::patch(&self.base.config, config);
+            let resolved = ResolvedConfig::new(Some(dir.to_path_buf()), config)?
+                .with_warnings(warnings)
+                .with_bases(Config::bases(&path)?);
+            Arc::new(resolved)
+        } else {
+            match dir.parent() {
+                Some(parent) if !Config::is_root(dir)? => self.resolve_dir(parent, fallback)?,
+                _ => Arc::clone(fallback),
+            }
+        };
+
+        self.cache
+            .write()
+            .map_err(|_err| miette!("Failed to acquire lock"))?
+            .insert(dir.to_path_buf(), Arc::clone(&resolved));
+        Ok(resolved)
+    }
+
+    /// Returns the warnings of all configurations resolved so far without duplicates
+    #[inline]
+    pub fn warnings(&self) -> Result<Vec<String>> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|_err| miette!("Failed to acquire lock"))?;
+        let warnings: BTreeSet<_> = self
+            .fallback
+            .iter()
+            .chain(cache.values())
+            .flat_map(|resolved| resolved.warnings.iter().cloned())
+            .collect();
+        drop(cache);
+        Ok(warnings.into_iter().collect())
+    }
+
+    /// Returns the files extended by all configurations resolved so far without duplicates
+    #[inline]
+    pub fn bases(&self) -> Result<Vec<PathBuf>> {
+        let cache = self
+            .cache
+            .read()
+            .map_err(|_err| miette!("Failed to acquire lock"))?;
+        let bases: BTreeSet<_> = cache
+            .values()
+            .flat_map(|resolved| resolved.bases.iter().cloned())
+            .collect();
+        drop(cache);
+        Ok(bases.into_iter().collect())
+    }
+
+    const fn patch(base: &Config, config: Config) -> Config {
+        let mut config = config;
+        config.lint.report_unused_suppressions |= base.lint.report_unused_suppressions;
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use globset::Glob;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::config::lint::{Override, RuleSet};
+
+    #[test]
+    fn resolve_without_discovery() -> Result<()> {
+        let config = Config::default();
+        let resolver = ConfigResolver::new(config.clone())?;
+        let actual = resolver.resolve(Path::new("README.md"))?;
+        assert_eq!(actual.root, None);
+        assert_eq!(actual.config, config);
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_with_discovery() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let repo_dir = tmp_dir.path().join("repo");
+        let package_dir = repo_dir.join("packages").join("foo");
+        fs::create_dir_all(repo_dir.join(".git")).into_diagnostic()?;
+        fs::create_dir_all(package_dir.join("docs")).into_diagnostic()?;
+        fs::write(repo_dir.join("mado.toml"), "[lint]\nrules = []\n").into_diagnostic()?;
+        fs::write(
+            package_dir.join(".mado.toml"),
+            "[lint]\nrules = ['MD001']\n",
+        )
+        .into_diagnostic()?;
+
+        let mut config = Config::default();
+        config.lint.report_unused_suppressions = true;
+        let resolver = ConfigResolver::discover(config)?;
+
+        let repo_config = resolver.resolve(&repo_dir.join("README.md"))?;
+        assert_eq!(repo_config.root, Some(repo_dir));
+        assert_eq!(repo_config.config.lint.rules, vec![]);
+        assert!(repo_config.config.lint.report_unused_suppressions);
+
+        let package_config = resolver.resolve(&package_dir.join("docs").join("index.md"))?;
+        assert_eq!(package_config.root, Some(package_dir.clone()));
+        assert_eq!(package_config.config.lint.rules, vec![RuleSet::MD001]);
+
+        // Resolved configurations are cached per directory
+        let cached = resolver.resolve(&package_dir.join("docs").join("usage.md"))?;
+        assert!(Arc::ptr_eq(&package_config, &cached));
+
+        fs::write(
+            package_dir.join(".mado.toml"),
+            "[lint]\nrules = ['MD002']\n",
+        )
+        .into_diagnostic()?;
+        resolver.clear()?;
+        let reloaded = resolver.resolve(&package_dir.join("docs").join("usage.md"))?;
+        assert_eq!(reloaded.config.lint.rules, vec![RuleSet::MD002]);
+        Ok(())
+    }
+
+    #[test]
+    fn warnings() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        fs::create_dir(tmp_dir.path().join(".git")).into_diagnostic()?;
+        let docs_dir = tmp_dir.path().join("docs");
+        fs::create_dir(&docs_dir).into_diagnostic()?;
+        let path = tmp_dir.path().join(".markdownlint.json");
+        fs::write(&path, r#"{ "link-fragments": true }"#).into_diagnostic()?;
+        let docs_path = docs_dir.join(".markdownlint.json");
+        fs::write(&docs_path, r#"{ "link-fragments": true }"#).into_diagnostic()?;
+
+        let resolver = ConfigResolver::discover(Config::default())?;
+        assert_eq!(resolver.warnings()?, Vec::<String>::new());
+
+        resolver.resolve(&tmp_dir.path().join("README.md"))?;
+        resolver.resolve(&docs_dir.join("index.md"))?;
+        resolver.resolve(&docs_dir.join("usage.md"))?;
+        let expected = vec![
+            format!(
+                "rule `link-fragments` is not supported in {}",
+                path.display()
+            ),
+            format!(
+                "rule `link-fragments` is not supported in {}",
+                docs_path.display()
+            ),
+        ];
+        assert_eq!(resolver.warnings()?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn matched_overrides() -> Result<()> {
+        let mut config = Config::default();
+        config.lint.overrides = vec![Override {
+            files: vec![Glob::new("docs/*.md").into_diagnostic()?],
+            rules: None,
+            extend_rules: vec![],
+            disable_rules: vec![],
+            settings: toml::Table::new(),
+        }];
+
+        let base = ResolvedConfig::new(None, config.clone())?;
+        assert_eq!(base.matched_overrides(Path::new("./docs/a.md"))?, vec![0]);
+        assert_eq!(
+            base.matched_overrides(Path::new("a.md"))?,
+            Vec::<usize>::new()
+        );
+
+        let root = path::absolute("packages").into_diagnostic()?;
+        let discovered = ResolvedConfig::new(Some(root), config)?;
+        let path = Path::new("packages").join("docs").join("a.md");
+        assert_eq!(discovered.matched_overrides(&path)?, vec![0]);
+        assert_eq!(
+            discovered.matched_overrides(Path::new("docs/a.md"))?,
+            Vec::<usize>::new()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn is_excluded() -> Result<()> {
+        let mut config = Config::default();
+        config.lint.exclude = vec![Glob::new("drafts/*.md").into_diagnostic()?];
+
+        let base = ResolvedConfig::new(None, config.clone())?;
+        assert!(base.is_excluded(Path::new("./drafts/a.md"))?);
+        assert!(base.is_excluded(&path::absolute("drafts/a.md").into_diagnostic()?)?);
+        assert!(!base.is_excluded(Path::new("a.md"))?);
+
+        let root = path::absolute("packages").into_diagnostic()?;
+        let discovered = ResolvedConfig::new(Some(root), config)?;
+        let path = Path::new("packages").join("drafts").join("a.md");
+        assert!(discovered.is_excluded(&path)?);
+        assert!(!discovered.is_excluded(Path::new("drafts/a.md"))?);
+        Ok(())
+    }
+}
@@ -11,11 +11,25 @@ use miette::miette;
 use miette::{IntoDiagnostic as _, Result};
 
 use super::Linter;
+use super::cache::Cache;
+use super::resolver::ConfigResolver;
 use super::visitor::MarkdownLintVisitorFactory;
 use super::walker::WalkParallelBuilder;
 use crate::config::Config;
 use crate::{Document, Violation};
 
+/// Violations found by a run together with all the files that were checked
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct LintReport {
+    pub files: Vec<PathBuf>,
+    pub violations: Vec<Violation>,
+    /// Warnings about options of discovered configurations that are not enforced
+    pub warnings: Vec<String>,
+    /// Files extended by discovered configurations
+    pub config_bases: Vec<PathBuf>,
+}
+
 #[non_exhaustive]
 pub enum LintRunner {
     Parallel(Box<ParallelLintRunner>),
@@ -24,7 +38,7 @@ pub enum LintRunner {
 
 impl LintRunner {
     #[inline]
-    pub fn run(self) -> Result<Vec<Violation>> {
+    pub fn run(self) -> Result<LintReport> {
         match self {
             Self::Parallel(runner) => runner.run(),
             Self::String(runner) => runner.run(),
@@ -33,46 +47,84 @@ impl LintRunner {
 }
 
 pub struct ParallelLintRunner {
-    walker: WalkParallel,
+    walker: Option<WalkParallel>,
     config: Config,
     capacity: usize,
+    discover: bool,
+    cache: Option<Cache>,
 }
 
 impl ParallelLintRunner {
     #[inline]
     pub fn new(patterns: &[PathBuf], config: Config, capacity: usize) -> Result<Self> {
-        let walker = WalkParallelBuilder::build(
-            patterns,
-            config.lint.respect_ignore,
-            config.lint.respect_gitignore,
-        )?;
+        // Nothing is walked without patterns, e.g. when no files have changed
+        let walker = if patterns.is_empty() {
+            None
+        } else {
+            Some(WalkParallelBuilder::build(
+                patterns,
+                config.lint.respect_ignore,
+                config.lint.respect_gitignore,
+            )?)
+        };
 
         Ok(Self {
             walker,
             config,
             capacity,
+            discover: false,
+            cache: None,
         })
     }
 
+    /// Lints each file with the nearest configuration instead of `config`
+    #[inline]
+    #[must_use]
+    pub fn with_config_discovery(self) -> Self {
+        Self {
+            discover: true,
+            ..self
+        }
+    }
+
+    /// Reuses violations of unchanged files stored in `cache`
+    #[inline]
+    #[must_use]
+    pub fn with_cache(self, cache: Cache) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
     #[inline]
     // TODO: Don't use expect
     #[expect(clippy::expect_used)]
-    pub fn run(self) -> Result<Vec<Violation>> {
-        let mutex_violations: Arc<Mutex<Vec<Violation>>> = Arc::new(Mutex::new(vec![]));
-        let (tx, rx) = mpsc::sync_channel::<Vec<Violation>>(self.capacity);
+    pub fn run(self) -> Result<LintReport> {
+        let mutex_report: Arc<Mutex<LintReport>> = Arc::new(Mutex::new(LintReport::default()));
+        let (tx, rx) = mpsc::sync_channel::<(PathBuf, Vec<Violation>)>(self.capacity);
 
-        let local_mutex_violations = Arc::clone(&mutex_violations);
+        let local_mutex_report = Arc::clone(&mutex_report);
         let thread = thread::spawn(move || {
-            for violations in rx {
-                let mut acquired_violations = local_mutex_violations
-                    .lock()
-                    .expect("lock must be acquired");
-                acquired_violations.extend(violations);
+            for (path, violations) in rx {
+                let mut acquired_report = local_mutex_report.lock().expect("lock must be acquired");
+                acquired_report.files.push(path);
+                acquired_report.violations.extend(violations);
             }
         });
 
-        let mut builder = MarkdownLintVisitorFactory::new(self.config, tx)?;
-        self.walker.visit(&mut builder);
+        let resolver = Arc::new(if self.discover {
+            ConfigResolver::discover(self.config)?
+        } else {
+            ConfigResolver::new(self.config)?
+        });
+        let mut builder = MarkdownLintVisitorFactory::new(Arc::clone(&resolver), tx)?;
+        if let Some(cache) = &self.cache {
+            builder = builder.with_cache(cache.clone());
+        }
+        if let Some(walker) = self.walker {
+            walker.visit(&mut builder);
+        }
 
         // Wait for the completion
         drop(builder);
@@ -80,14 +132,21 @@ impl ParallelLintRunner {
             .join()
             .map_err(|err| miette!("Failed to join thread. {:?}", err))?;
 
-        // Take ownership of violations
-        let lock =
-            Arc::into_inner(mutex_violations).ok_or_else(|| miette!("Failed to unwrap Arc"))?;
-        lock.into_inner().into_diagnostic()
+        // Take ownership of the report
+        let lock = Arc::into_inner(mutex_report).ok_or_else(|| miette!("Failed to unwrap Arc"))?;
+        let mut report = lock.into_inner().into_diagnostic()?;
+        report.files.sort();
+        report.warnings = resolver.warnings()?;
+        report.config_bases = resolver.bases()?;
+        if let Some(cache) = &self.cache {
+            // Failing to prune entries only leaves unused files behind
+            cache.prune().ok();
+        }
+        Ok(report)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StringLintRunner {
     string: String,
     config: Config,
@@ -101,20 +160,31 @@ impl StringLintRunner {
     }
 
     #[inline]
-    pub fn run(self) -> Result<Vec<Violation>> {
+    pub fn run(self) -> Result<LintReport> {
         let arena = Arena::new();
         let path = Path::new("(stdin)").to_path_buf();
-        let doc = Document::new(&arena, path, self.string)?;
+        let doc = Document::new(&arena, path.clone(), self.string)?;
         let linter = Linter::from(&self.config);
-        linter.check(&doc)
+        Ok(LintReport {
+            files: vec![path],
+            violations: linter.check(&doc)?,
+            warnings: vec![],
+            config_bases: vec![],
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use core::slice;
+    use std::fs;
     use std::path::Path;
 
+    use comrak::nodes::Sourcepos;
     use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use crate::rule::{MD026, RuleLike as _};
 
     use super::*;
 
@@ -126,7 +196,55 @@ mod tests {
         let patterns = [Path::new(".").to_path_buf()];
         let runner = ParallelLintRunner::new(&patterns, config, 0)?;
         let actual = runner.run()?;
-        assert_eq!(actual, vec![]);
+        assert_eq!(actual.violations, vec![]);
+        assert!(
+            actual
+                .files
+                .contains(&Path::new("./README.md").to_path_buf())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parallel_lint_runner_run_with_cache() -> Result<()> {
+        let tmp_dir = tempdir().into_diagnostic()?;
+        let path = tmp_dir.path().join("test.md");
+        let text = "# Hello\n";
+        fs::write(&path, text).into_diagnostic()?;
+        let cache_dir = tmp_dir.path().join(Cache::DIR);
+        let config = Config::default();
+        let patterns = [path.clone()];
+
+        let run = || -> Result<LintReport> {
+            let runner = ParallelLintRunner::new(&patterns, config.clone(), 0)?;
+            runner.with_cache(Cache::open(&cache_dir)?).run()
+        };
+        assert_eq!(run()?.violations, vec![]);
+
+        // Stored violations are returned as long as neither the file nor the configuration changes
+        let violation = MD026::default().to_violation(path.clone(), Sourcepos::from((1, 1, 1, 7)));
+        let config_key = Cache::config_key(&config.lint)?;
+        let cache = Cache::open(&cache_dir)?;
+        let key = Cache::key(config_key, text);
+        cache.set(&path, key, slice::from_ref(&violation))?;
+        assert_eq!(run()?.violations, vec![violation]);
+
+        fs::write(&path, "# Hello!\n").into_diagnostic()?;
+        let changed = run()?;
+        assert_eq!(changed.violations.len(), 1);
+        assert_eq!(
+            changed.violations[0].position(),
+            &Sourcepos::from((1, 1, 1, 8))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn string_lint_runner_run() -> Result<()> {
+        let runner = StringLintRunner::new("#Hello\n".to_owned(), Config::default());
+        let actual = runner.run()?;
+        assert_eq!(actual.files, vec![Path::new("(stdin)").to_path_buf()]);
+        assert_eq!(actual.violations.len(), 2);
         Ok(())
     }
 }
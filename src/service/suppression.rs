@@ -0,0 +1,443 @@
+use core::cell::RefCell;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use comrak::nodes::{LineColumn, NodeValue, Sourcepos};
+use regex::Regex;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::config::lint::RuleSet;
+use crate::rule::Metadata;
+use crate::{Document, Violation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Disable,
+    Enable,
+    DisableLine,
+    DisableNextLine,
+    DisableFile,
+    EnableFile,
+    Capture,
+    Restore,
+}
+
+impl Action {
+    fn parse(action: &str) -> Option<Self> {
+        match action.to_ascii_lowercase().as_str() {
+            "disable" => Some(Self::Disable),
+            "enable" => Some(Self::Enable),
+            "disable-line" => Some(Self::DisableLine),
+            "disable-next-line" => Some(Self::DisableNextLine),
+            "disable-file" => Some(Self::DisableFile),
+            "enable-file" => Some(Self::EnableFile),
+            "capture" => Some(Self::Capture),
+            "restore" => Some(Self::Restore),
+            _ => None,
+        }
+    }
+
+    const fn is_disable(self) -> bool {
+        matches!(
+            self,
+            Self::Disable | Self::DisableLine | Self::DisableNextLine | Self::DisableFile
+        )
+    }
+}
+
+/// An inline comment such as `<!-- markdownlint-disable MD013 -->`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Directive {
+    action: Action,
+    position: Sourcepos,
+    // NOTE: Empty means all rules
+    names: Vec<String>,
+}
+
+impl Directive {
+    // Returns rule names for the names and aliases in the comment, or `None` for all rules
+    fn rules(&self) -> Option<Vec<&'static str>> {
+        if self.names.is_empty() {
+            return None;
+        }
+
+        let rules = self
+            .names
+            .iter()
+            .filter_map(|name| RuleSet::rule_name(name))
+            .collect();
+        Some(rules)
+    }
+}
+
+/// Rules disabled at some point, mapped to the index of the disabling directive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct State {
+    disabled_by_default: Option<usize>,
+    exceptions: FxHashMap<&'static str, Option<usize>>,
+}
+
+impl State {
+    fn disabled_by(&self, name: &str) -> Option<usize> {
+        self.exceptions
+            .get(name)
+            .map_or(self.disabled_by_default, |disabled_by| *disabled_by)
+    }
+
+    fn set(&mut self, names: Option<&[&'static str]>, enabled: bool, index: usize) {
+        let disabled_by = (!enabled).then_some(index);
+        let Some(names) = names else {
+            self.disabled_by_default = disabled_by;
+            self.exceptions.clear();
+            return;
+        };
+
+        for name in names {
+            self.exceptions.insert(name, disabled_by);
+        }
+    }
+}
+
+/// Rules disabled by inline comments, compatible with markdownlint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    directives: Vec<Directive>,
+    initial_state: State,
+    // NOTE: Sorted by line, each state is in effect from the line onward
+    states: Vec<(usize, State)>,
+    line_states: FxHashMap<usize, State>,
+    used: RefCell<FxHashSet<(usize, String)>>,
+}
+
+impl Suppressions {
+    #[inline]
+    #[must_use]
+    pub fn new(doc: &Document) -> Self {
+        let directives = Self::directives(doc);
+        if directives.is_empty() {
+            return Self::default();
+        }
+
+        // File-level directives are applied to the whole file regardless of their position
+        let mut initial_state = State::default();
+        for (index, directive) in directives.iter().enumerate() {
+            if let Action::DisableFile | Action::EnableFile = directive.action {
+                let enabled = directive.action == Action::EnableFile;
+                initial_state.set(directive.rules().as_deref(), enabled, index);
+            }
+        }
+
+        let mut state = initial_state.clone();
+        let mut captured_states = vec![];
+        let mut states = vec![];
+        let mut line_states: FxHashMap<usize, State> = FxHashMap::default();
+        for (index, directive) in directives.iter().enumerate() {
+            let rules = directive.rules();
+            match directive.action {
+                Action::Disable | Action::Enable => {
+                    let enabled = directive.action == Action::Enable;
+                    state.set(rules.as_deref(), enabled, index);
+                    states.push((directive.position.start.line, state.clone()));
+                }
+                Action::Capture => captured_states.push(state.clone()),
+                Action::Restore => {
+                    state = captured_states
+                        .pop()
+                        .unwrap_or_else(|| initial_state.clone());
+                    states.push((directive.position.start.line, state.clone()));
+                }
+                Action::DisableLine => line_states
+                    .entry(directive.position.start.line)
+                    .or_default()
+                    .set(rules.as_deref(), false, index),
+                Action::DisableNextLine => line_states
+                    .entry(directive.position.end.line + 1)
+                    .or_default()
+                    .set(rules.as_deref(), false, index),
+                Action::DisableFile | Action::EnableFile => {}
+            }
+        }
+
+        Self {
+            directives,
+            initial_state,
+            states,
+            line_states,
+            used: RefCell::default(),
+        }
+    }
+
+    /// Returns true if the rule is disabled by inline comments at the given 1-based line,
+    /// and marks the disabling comment as used
+    #[inline]
+    #[must_use]
+    pub fn suppress(&self, name: &str, lineno: usize) -> bool {
+        let Some(index) = self.disabled_by(name, lineno) else {
+            return false;
+        };
+
+        self.used.borrow_mut().insert((index, name.to_owned()));
+        true
+    }
+
+    /// Returns violations for comments which disabled no violations or refer to unknown rules
+    #[inline]
+    #[must_use]
+    pub fn unused(&self, path: &Path) -> Vec<Violation> {
+        let used = self.used.borrow();
+        let mut violations = vec![];
+        for (index, directive) in self.directives.iter().enumerate() {
+            let is_unknown = directive
+                .names
+                .iter()
+                .any(|name| RuleSet::rule_name(name).is_none());
+            if is_unknown {
+                let metadata = &Metadata::UNKNOWN_SUPPRESSION;
+                violations.push(Violation::new(
+                    path.to_path_buf(),
+                    metadata,
+                    directive.position,
+                ));
+            }
+
+            if !directive.action.is_disable() {
+                continue;
+            }
+
+            let is_unused = directive.rules().map_or_else(
+                || !used.iter().any(|(used_index, _)| *used_index == index),
+                |rules| {
+                    rules
+                        .iter()
+                        .any(|name| !used.contains(&(index, (*name).to_owned())))
+                },
+            );
+            if is_unused {
+                let metadata = &Metadata::UNUSED_SUPPRESSION;
+                violations.push(Violation::new(
+                    path.to_path_buf(),
+                    metadata,
+                    directive.position,
+                ));
+            }
+        }
+
+        violations
+    }
+
+    // NOTE: A line-level comment takes precedence as it is more specific
+    fn disabled_by(&self, name: &str, lineno: usize) -> Option<usize> {
+        if let Some(index) = self
+            .line_states
+            .get(&lineno)
+            .and_then(|line_state| line_state.disabled_by(name))
+        {
+            return Some(index);
+        }
+
+        let index = self.states.partition_point(|(line, _)| *line <= lineno);
+        index
+            .checked_sub(1)
+            .and_then(|i| self.states.get(i))
+            .map_or(&self.initial_state, |(_, state)| state)
+            .disabled_by(name)
+    }
+
+    fn directives(doc: &Document) -> Vec<Directive> {
+        static RE: LazyLock<Regex> = LazyLock::new(|| {
+            #[allow(clippy::unwrap_used)]
+            Regex::new(r"(?i)<!--\s*(?:markdownlint|mado)-(disable-next-line|disable-line|disable-file|enable-file|disable|enable|capture|restore)((?:\s+[\w-]+)*)\s*-->").unwrap()
+        });
+
+        let mut directives = vec![];
+        for node in doc.ast.descendants() {
+            let data = node.data.borrow();
+            let literal = match &data.value {
+                NodeValue::HtmlBlock(block) => &block.literal,
+                NodeValue::HtmlInline(literal) => literal,
+                _ => continue,
+            };
+
+            for captures in RE.captures_iter(literal) {
+                let (Some(m), Some(action), Some(names)) =
+                    (captures.get(0), captures.get(1), captures.get(2))
+                else {
+                    continue;
+                };
+                let Some(action) = Action::parse(action.as_str()) else {
+                    continue;
+                };
+
+                let start = data.sourcepos.start;
+                let position = Sourcepos {
+                    start: Self::line_column(start, literal, m.start()),
+                    end: Self::line_column(start, literal, m.end() - 1),
+                };
+                let names = names
+                    .as_str()
+                    .split_whitespace()
+                    .map(ToOwned::to_owned)
+                    .collect();
+                directives.push(Directive {
+                    action,
+                    position,
+                    names,
+                });
+            }
+        }
+
+        directives
+    }
+
+    // Returns the position of the byte offset in the literal of an HTML node starting at `start`
+    fn line_column(start: LineColumn, literal: &str, offset: usize) -> LineColumn {
+        let prefix = literal.get(..offset).unwrap_or_default();
+        let line_start = prefix.rfind('\n').map_or(0, |newline| newline + 1);
+        LineColumn {
+            line: start.line + prefix.matches('\n').count(),
+            column: start.column + offset - line_start,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comrak::Arena;
+    use indoc::indoc;
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn suppressed_lines(text: &str, name: &str) -> Result<Vec<usize>> {
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path, text.to_owned())?;
+        let suppressions = Suppressions::new(&doc);
+        let lines = (1..=doc.lines.len())
+            .filter(|lineno| suppressions.suppress(name, *lineno))
+            .collect();
+        Ok(lines)
+    }
+
+    #[test]
+    fn suppress_disable_enable() -> Result<()> {
+        let text = indoc! {"
+            Text
+            <!-- markdownlint-disable MD013 MD033 -->
+            Text
+            <!-- markdownlint-enable line-length -->
+            Text
+        "};
+        assert_eq!(suppressed_lines(text, "MD013")?, vec![2, 3]);
+        assert_eq!(suppressed_lines(text, "MD033")?, vec![2, 3, 4, 5]);
+        assert_eq!(suppressed_lines(text, "MD009")?, Vec::<usize>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn suppress_disable_all() -> Result<()> {
+        let text = indoc! {"
+            Text <!-- mado-disable -->
+            Text
+            <!-- MADO-ENABLE MD009 -->
+        "};
+        assert_eq!(suppressed_lines(text, "MD013")?, vec![1, 2, 3]);
+        assert_eq!(suppressed_lines(text, "MD009")?, vec![1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn suppress_disable_line() -> Result<()> {
+        let text = indoc! {"
+            <!-- markdownlint-disable-next-line no-trailing-spaces -->
+            Text
+            Text <!-- markdownlint-disable-line -->
+            Text
+        "};
+        assert_eq!(suppressed_lines(text, "MD009")?, vec![2, 3]);
+        assert_eq!(suppressed_lines(text, "MD013")?, vec![3]);
+        Ok(())
+    }
+
+    #[test]
+    fn suppress_capture_restore() -> Result<()> {
+        let text = indoc! {"
+            <!-- markdownlint-disable MD013 -->
+            Text
+            <!-- markdownlint-capture -->
+            <!-- markdownlint-enable MD013 -->
+            <!-- markdownlint-disable MD009 -->
+            Text
+            <!-- markdownlint-restore -->
+            Text
+        "};
+        assert_eq!(suppressed_lines(text, "MD013")?, vec![1, 2, 3, 7, 8]);
+        assert_eq!(suppressed_lines(text, "MD009")?, vec![5, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn suppress_disable_file() -> Result<()> {
+        let text = indoc! {"
+            Text
+
+            <!-- markdownlint-disable-file MD013 -->
+        "};
+        assert_eq!(suppressed_lines(text, "MD013")?, vec![1, 2, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn suppress_in_code_block() -> Result<()> {
+        let text = indoc! {"
+            ```html
+            <!-- markdownlint-disable -->
+            ```
+            Text
+        "};
+        assert_eq!(suppressed_lines(text, "MD013")?, Vec::<usize>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn unused() -> Result<()> {
+        let text = indoc! {"
+            <!-- markdownlint-disable MD013 MD033 -->
+            Text
+
+            > Text <!-- mado-disable-line no-bare-urls -->
+            <!-- markdownlint-enable -->
+
+            <!-- markdownlint-disable-next-line MD009 unknown-rule -->
+            Text
+        "};
+        let path = Path::new("test.md").to_path_buf();
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.clone(), text.to_owned())?;
+        let suppressions = Suppressions::new(&doc);
+        assert!(suppressions.suppress("MD013", 2));
+        assert!(!suppressions.suppress("MD034", 2));
+        assert!(suppressions.suppress("MD009", 8));
+        let actual = suppressions.unused(&path);
+        let expected = vec![
+            Violation::new(
+                path.clone(),
+                &Metadata::UNUSED_SUPPRESSION,
+                Sourcepos::from((1, 1, 1, 41)),
+            ),
+            Violation::new(
+                path.clone(),
+                &Metadata::UNUSED_SUPPRESSION,
+                Sourcepos::from((4, 8, 4, 46)),
+            ),
+            Violation::new(
+                path,
+                &Metadata::UNKNOWN_SUPPRESSION,
+                Sourcepos::from((7, 1, 7, 58)),
+            ),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
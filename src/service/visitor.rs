@@ -1,61 +1,170 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
 use core::result::Result;
-use std::path::{Component, PathBuf};
+use std::collections::hash_map::Entry;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 use std::sync::mpsc::SyncSender;
 
 use comrak::Arena;
 use globset::GlobSet;
 use ignore::{DirEntry, Error, ParallelVisitor, ParallelVisitorBuilder, WalkState};
 use miette::IntoDiagnostic as _;
+use rustc_hash::FxHashMap;
 
 use super::Linter;
-use crate::{Document, Violation, config::Config};
+use super::cache::Cache;
+use super::resolver::ConfigResolver;
+use crate::config::Config;
+use crate::{Document, Violation};
 
 pub struct MarkdownLintVisitor {
-    linter: Linter,
+    resolver: Arc<ConfigResolver>,
+    // Linters and the cache keys of their configurations, keyed by the directory of
+    // the configuration and the indices of matched overrides
+    linters: FxHashMap<(Option<PathBuf>, Vec<usize>), (Linter, u128)>,
     exclusion: GlobSet,
-    tx: SyncSender<Vec<Violation>>,
+    fix: bool,
+    cache: Option<Arc<Cache>>,
+    tx: SyncSender<(PathBuf, Vec<Violation>)>,
 }
 
 impl MarkdownLintVisitor {
     #[inline]
     #[must_use]
-    pub const fn new(linter: Linter, exclusion: GlobSet, tx: SyncSender<Vec<Violation>>) -> Self {
+    pub fn new(
+        resolver: Arc<ConfigResolver>,
+        exclusion: GlobSet,
+        fix: bool,
+        tx: SyncSender<(PathBuf, Vec<Violation>)>,
+    ) -> Self {
         Self {
-            linter,
+            resolver,
+            linters: FxHashMap::default(),
             exclusion,
+            fix,
+            cache: None,
             tx,
         }
     }
 
-    fn visit_inner(&self, either_entry: Result<DirEntry, Error>) -> miette::Result<()> {
+    /// Reuses violations of unchanged files stored in `cache` unless fixing files
+    #[inline]
+    #[must_use]
+    pub fn with_cache(self, cache: Arc<Cache>) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    // Returns `None` if `path` is excluded by its nearest configuration
+    fn linter(&mut self, path: &Path) -> miette::Result<Option<&(Linter, u128)>> {
+        let resolved = self.resolver.resolve(path)?;
+        if resolved.is_excluded(path)? {
+            return Ok(None);
+        }
+
+        let indices = resolved.matched_overrides(path)?;
+        match self.linters.entry((resolved.root.clone(), indices)) {
+            Entry::Occupied(entry) => Ok(Some(entry.into_mut())),
+            Entry::Vacant(entry) => {
+                let lint = resolved.config.lint.overridden(&entry.key().1)?;
+                let config_key = Cache::config_key(&lint)?;
+                let linter = Linter::from(&Config {
+                    extends: vec![],
+                    lint,
+                });
+                Ok(Some(entry.insert((linter, config_key))))
+            }
+        }
+    }
+
+    fn check_cached(
+        linter: &Linter,
+        cache: &Cache,
+        config_key: u128,
+        path: &Path,
+    ) -> miette::Result<Vec<Violation>> {
+        let text = fs::read_to_string(path).into_diagnostic()?;
+        let key = Cache::key(config_key, &text);
+        if let Some(violations) = cache.get(path, key, linter) {
+            return Ok(violations);
+        }
+
+        let arena = Arena::new();
+        let doc = Document::new(&arena, path.to_path_buf(), text)?;
+        let violations = linter.check(&doc)?;
+        // Failing to store violations only makes the next run slower
+        cache.set(path, key, &violations).ok();
+        Ok(violations)
+    }
+
+    fn open<'a>(
+        linter: &Linter,
+        fix: bool,
+        arena: &'a Arena<'a>,
+        path: &Path,
+    ) -> miette::Result<Document<'a>> {
+        if !fix {
+            return Document::open(arena, path);
+        }
+
+        let text = fs::read_to_string(path).into_diagnostic()?;
+        let fixed = linter.fix(path, text.clone())?;
+        if fixed != text {
+            fs::write(path, &fixed).into_diagnostic()?;
+        }
+
+        Document::new(arena, path.to_path_buf(), fixed)
+    }
+
+    fn visit_inner(&mut self, either_entry: Result<DirEntry, Error>) -> miette::Result<()> {
         let entry = either_entry.into_diagnostic()?;
         let path = entry.path();
-        if path.is_file() && path.extension() == Some("md".as_ref()) {
-            // Strip a leading "./" so that exclude patterns match regardless of
-            // whether the walked path carries one (depends on how the target
-            // argument was spelled on the command line, see issue #168). Keep
-            // this in sync with the pattern normalization in
-            // Lint::exclude_set (src/config/lint.rs) or the two sides stop
-            // agreeing on what a match is.
-            let normalized_path: PathBuf = path
-                .components()
-                .skip_while(|component| matches!(component, Component::CurDir))
-                .collect();
-
-            if !self.exclusion.is_match(&normalized_path) {
-                let arena = Arena::new();
-                let doc = Document::open(&arena, path)?;
-                let violations = self.linter.check(&doc)?;
-                if !violations.is_empty() {
-                    self.tx.send(violations).into_diagnostic()?;
+        if is_markdown_file(path, &self.exclusion) {
+            let fix = self.fix;
+            let cache = self.cache.clone();
+            let Some((linter, config_key)) = self.linter(path)? else {
+                return Ok(());
+            };
+            let violations = match cache {
+                Some(cache) if !fix => Self::check_cached(linter, &cache, *config_key, path)?,
+                _ => {
+                    let arena = Arena::new();
+                    let doc = Self::open(linter, fix, &arena, path)?;
+                    linter.check(&doc)?
                 }
-            }
+            };
+            self.tx
+                .send((path.to_path_buf(), violations))
+                .into_diagnostic()?;
         }
 
         Ok(())
     }
 }
 
+// Strip a leading "./" so that exclude patterns match regardless of whether
+// the walked path carries one (depends on how the target argument was spelled
+// on the command line, see issue #168). Keep this in sync with the pattern
+// normalization in Lint::exclude_set (src/config/lint.rs) or the two sides
+// stop agreeing on what a match is.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .skip_while(|component| matches!(component, Component::CurDir))
+        .collect()
+}
+
+/// Returns true if `path` is a Markdown file not excluded by `exclusion` of the
+/// configuration given for the whole run
+pub(crate) fn is_markdown_file(path: &Path, exclusion: &GlobSet) -> bool {
+    path.is_file()
+        && path.extension() == Some("md".as_ref())
+        && !exclusion.is_match(normalize_path(path))
+}
+
 impl ParallelVisitor for MarkdownLintVisitor {
     #[inline]
     fn visit(&mut self, either_entry: Result<DirEntry, Error>) -> WalkState {
@@ -68,32 +177,51 @@ impl ParallelVisitor for MarkdownLintVisitor {
 }
 
 pub struct MarkdownLintVisitorFactory {
-    config: Config,
+    resolver: Arc<ConfigResolver>,
     exclusion: GlobSet,
-    tx: SyncSender<Vec<Violation>>,
+    cache: Option<Arc<Cache>>,
+    tx: SyncSender<(PathBuf, Vec<Violation>)>,
 }
 
 impl MarkdownLintVisitorFactory {
     #[inline]
-    pub fn new(config: Config, tx: SyncSender<Vec<Violation>>) -> miette::Result<Self> {
-        let exclusion = config.lint.exclude_set()?;
+    pub fn new(
+        resolver: Arc<ConfigResolver>,
+        tx: SyncSender<(PathBuf, Vec<Violation>)>,
+    ) -> miette::Result<Self> {
+        let exclusion = resolver.config().lint.exclude_set()?;
         Ok(Self {
-            config,
+            resolver,
             exclusion,
+            cache: None,
             tx,
         })
     }
+
+    /// Builds visitors reusing violations stored in `cache`
+    #[inline]
+    #[must_use]
+    pub fn with_cache(self, cache: Cache) -> Self {
+        Self {
+            cache: Some(Arc::new(cache)),
+            ..self
+        }
+    }
 }
 
 impl<'s> ParallelVisitorBuilder<'s> for MarkdownLintVisitorFactory {
     #[inline]
     fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
-        let linter = Linter::from(&self.config);
-        Box::new(MarkdownLintVisitor::new(
-            linter,
+        let visitor = MarkdownLintVisitor::new(
+            Arc::clone(&self.resolver),
             self.exclusion.clone(),
+            self.resolver.config().lint.fix,
             self.tx.clone(),
-        ))
+        );
+        match &self.cache {
+            Some(cache) => Box::new(visitor.with_cache(Arc::clone(cache))),
+            None => Box::new(visitor),
+        }
     }
 }
 
@@ -105,19 +233,26 @@ mod tests {
 
     use super::*;
 
+    // Large enough to hold the results of all Markdown files in this repository
+    const CAPACITY: usize = 1024;
+
     #[test]
     fn markdown_lint_visitor_visit_inner() -> miette::Result<()> {
-        let (tx, rx) = mpsc::sync_channel::<Vec<Violation>>(0);
-        let linter = Linter::new(vec![]);
+        let (tx, rx) = mpsc::sync_channel::<(PathBuf, Vec<Violation>)>(CAPACITY);
+        let mut config = Config::default();
+        config.lint.rules = vec![];
+        let resolver = Arc::new(ConfigResolver::new(config)?);
         let exclusion = GlobSet::empty();
-        let visitor = MarkdownLintVisitor::new(linter, exclusion, tx);
+        let mut visitor = MarkdownLintVisitor::new(resolver, exclusion, false, tx);
 
         for entry in Walk::new(".") {
             visitor.visit_inner(entry)?;
         }
 
         drop(visitor);
-        assert!(rx.recv().is_err()); // Because rx has not received any messages
+        let results: Vec<_> = rx.iter().collect();
+        assert!(results.iter().any(|(path, _)| path.ends_with("README.md")));
+        assert!(results.iter().all(|(_, violations)| violations.is_empty()));
         Ok(())
     }
 
@@ -126,8 +261,9 @@ mod tests {
         let mut config = Config::default();
         config.lint.rules = vec![];
 
-        let (tx, rx) = mpsc::sync_channel::<Vec<Violation>>(0);
-        let mut factory = MarkdownLintVisitorFactory::new(config, tx)?;
+        let (tx, rx) = mpsc::sync_channel::<(PathBuf, Vec<Violation>)>(CAPACITY);
+        let resolver = Arc::new(ConfigResolver::new(config)?);
+        let mut factory = MarkdownLintVisitorFactory::new(resolver, tx)?;
         let mut visitor = factory.build();
 
         for entry in Walk::new(".") {
@@ -136,7 +272,9 @@ mod tests {
 
         drop(visitor);
         drop(factory);
-        assert!(rx.recv().is_err()); // Because rx has not received any messages
+        let results: Vec<_> = rx.iter().collect();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|(_, violations)| violations.is_empty()));
         Ok(())
     }
 }
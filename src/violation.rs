@@ -2,14 +2,18 @@ use core::cmp::Ordering;
 use std::path::PathBuf;
 
 use comrak::nodes::Sourcepos;
+use xxhash_rust::xxh3::xxh3_64;
 
-use crate::rule::Metadata;
+use crate::fix::Edit;
+use crate::rule::{Metadata, Severity, Tag};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Violation {
     path: PathBuf,
     metadata: &'static Metadata,
     position: Sourcepos,
+    fixes: Vec<Edit>,
+    severity: Severity,
 }
 
 impl Violation {
@@ -20,9 +24,24 @@ impl Violation {
             path,
             metadata,
             position,
+            fixes: vec![],
+            severity: Severity::Error,
         }
     }
 
+    #[inline]
+    #[must_use]
+    pub fn with_severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    /// Attaches suggested replacements resolving this violation
+    #[inline]
+    #[must_use]
+    pub fn with_fixes(self, fixes: Vec<Edit>) -> Self {
+        Self { fixes, ..self }
+    }
+
     #[inline]
     #[must_use]
     pub const fn path(&self) -> &PathBuf {
@@ -47,11 +66,45 @@ impl Violation {
         self.metadata.description
     }
 
+    #[inline]
+    #[must_use]
+    pub const fn tags(&self) -> &[Tag] {
+        self.metadata.tags
+    }
+
     #[inline]
     #[must_use]
     pub const fn position(&self) -> &Sourcepos {
         &self.position
     }
+
+    #[inline]
+    #[must_use]
+    pub fn fixes(&self) -> &[Edit] {
+        &self.fixes
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns a hash of the offending lines in `source` without surrounding whitespace,
+    /// so that neither line numbers nor indentation changes affect it
+    #[inline]
+    #[must_use]
+    pub fn fingerprint(&self, source: &str) -> u64 {
+        let first = self.position.start.line.saturating_sub(1);
+        let last = self.position.end.line.max(self.position.start.line);
+        let lines: Vec<&str> = source
+            .lines()
+            .skip(first)
+            .take(last - first)
+            .map(str::trim)
+            .collect();
+        xxh3_64(lines.join("\n").as_bytes())
+    }
 }
 
 impl PartialOrd for Violation {
@@ -83,11 +136,26 @@ mod tests {
     use std::path::Path;
 
     use comrak::nodes::Sourcepos;
-    use pretty_assertions::assert_eq;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use xxhash_rust::xxh3::xxh3_64;
 
     use crate::rule::RuleLike as _;
     use crate::rule::{MD001, MD010};
 
+    #[test]
+    fn fingerprint() {
+        let path = Path::new("foo.md").to_path_buf();
+        let violation = MD001::new().to_violation(path.clone(), Sourcepos::from((2, 1, 3, 1)));
+        let moved_violation = MD001::new().to_violation(path, Sourcepos::from((3, 1, 4, 1)));
+        let fingerprint = violation.fingerprint("# Title\nfoo\n  bar\n");
+        assert_eq!(fingerprint, xxh3_64(b"foo\nbar"));
+        assert_eq!(
+            moved_violation.fingerprint("# Title\n\nfoo\nbar\n"),
+            fingerprint
+        );
+        assert_ne!(violation.fingerprint("# Title\nfoo\nbaz\n"), fingerprint);
+    }
+
     #[test]
     #[allow(clippy::similar_names)]
     fn cmp() {
@@ -0,0 +1,42 @@
+use std::fs;
+
+use assert_cmd::Command;
+use assert_cmd::cargo_bin;
+use miette::IntoDiagnostic as _;
+use miette::Result;
+use tempfile::tempdir;
+
+#[test]
+fn cache_clean() -> Result<()> {
+    let tmp_dir = tempdir().into_diagnostic()?;
+    let dir = tmp_dir.path().canonicalize().into_diagnostic()?;
+    fs::create_dir(dir.join(".git")).into_diagnostic()?;
+    fs::create_dir(dir.join("docs")).into_diagnostic()?;
+    fs::write(dir.join("docs").join("README.md"), "# Hello\n").into_diagnostic()?;
+
+    // The cache is placed at the repository root wherever mado runs
+    let cache_dir = dir.join(".mado_cache");
+    Command::new(cargo_bin!("mado"))
+        .current_dir(dir.join("docs"))
+        .args(["check", "."])
+        .assert()
+        .success();
+    assert!(cache_dir.is_dir());
+    assert!(!dir.join("docs").join(".mado_cache").exists());
+
+    Command::new(cargo_bin!("mado"))
+        .current_dir(dir.join("docs"))
+        .args(["cache", "clean"])
+        .assert()
+        .success()
+        .stdout(format!("Removed {}\n", cache_dir.display()));
+    assert!(!cache_dir.exists());
+
+    Command::new(cargo_bin!("mado"))
+        .current_dir(&dir)
+        .args(["cache", "clean"])
+        .assert()
+        .success()
+        .stdout(format!("No cache found at {}\n", cache_dir.display()));
+    Ok(())
+}
@@ -1,6 +1,6 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write as _;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use assert_cmd::Command;
 use assert_cmd::cargo_bin;
@@ -95,6 +95,629 @@ fn check_stdin_no_color() {
     "});
 }
 
+#[test]
+fn check_stdin_json_lines() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "json-lines"])
+        .assert();
+    assert.failure().stdout(indoc! {r#"
+        {"path":"(stdin)","rule":"MD026","alias":"no-trailing-punctuation","severity":"error","description":"Trailing punctuation in header","tags":["headers"],"position":{"start":{"line":1,"column":1},"end":{"line":1,"column":8}}}
+    "#});
+}
+
+#[test]
+fn check_json_without_violations() -> Result<()> {
+    with_tmp_file("test.md", "# Hello\n", |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd
+            .args(["check", "--output-format", "json", path_str])
+            .assert();
+        assert.success().stdout(indoc! {r#"
+            {
+              "version": 1,
+              "violations": [],
+              "summary": {
+                "violations": 0,
+                "files": 1
+              }
+            }
+        "#});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_stdin_sarif() -> Result<()> {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let output = cmd
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "sarif"])
+        .output()
+        .into_diagnostic()?;
+    assert!(!output.status.success());
+
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).into_diagnostic()?;
+    assert_eq!(log["version"], "2.1.0");
+    let results = &log["runs"][0]["results"];
+    assert_eq!(results.as_array().map(Vec::len), Some(1));
+    assert_eq!(results[0]["ruleId"], "MD026");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn check_junit() -> Result<()> {
+    with_tmp_file("a.md", "# Hello\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::write(dir.join("b.md"), "# Hello.\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .args(["check", "--output-format", "junit"])
+            .assert()
+            .failure()
+            .stdout(indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites name="mado" tests="2" failures="1" errors="0">
+              <testsuite name="mado" tests="2" failures="1" errors="0" skipped="0">
+                <testcase name="./a.md" classname="./a.md"/>
+                <testcase name="./b.md" classname="./b.md">
+                  <failure message="MD026 Trailing punctuation in header" type="MD026">./b.md:1:1: MD026 Trailing punctuation in header</failure>
+                </testcase>
+              </testsuite>
+            </testsuites>
+        "#});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_stdin_checkstyle() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "checkstyle"])
+        .assert();
+    assert.failure().stdout(indoc! {r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <checkstyle version="4.3">
+          <file name="(stdin)">
+            <error line="1" column="1" severity="error" message="Trailing punctuation in header" source="mado.MD026"/>
+          </file>
+        </checkstyle>
+    "#});
+}
+
+#[test]
+fn check_github_actions() -> Result<()> {
+    with_tmp_file("test.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env("GITHUB_ACTIONS", "true")
+            .args(["check"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ::error file=test.md,line=1,col=1,endLine=1,endColumn=8,title=MD026::Trailing punctuation in header
+
+            Found 1 error.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_github_actions_with_output_format() -> Result<()> {
+    with_tmp_file("test.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env("GITHUB_ACTIONS", "true")
+            .args(["check", "--output-format", "mdl"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./test.md:1: MD026 Trailing punctuation in header
+
+            Found 1 error.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_stdin_gitlab() -> Result<()> {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let output = cmd
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "gitlab"])
+        .output()
+        .into_diagnostic()?;
+    assert!(!output.status.success());
+
+    let issues: serde_json::Value = serde_json::from_slice(&output.stdout).into_diagnostic()?;
+    assert_eq!(issues.as_array().map(Vec::len), Some(1));
+    assert_eq!(issues[0]["check_name"], "MD026");
+    assert_eq!(issues[0]["location"]["path"], "(stdin)");
+    assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+    assert!(issues[0]["fingerprint"].is_string());
+    Ok(())
+}
+
+#[test]
+fn check_stdin_html() -> Result<()> {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let output = cmd
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "html"])
+        .output()
+        .into_diagnostic()?;
+    assert!(!output.status.success());
+
+    let html = String::from_utf8(output.stdout).into_diagnostic()?;
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<p>Found 1 error in 1 of 1 files.</p>"));
+    assert!(html.contains("<h3>(stdin) (1)</h3>"));
+    assert!(
+        html.contains(
+            r#"<span class="line highlight"><span class="number">1</span># Hello.</span>"#
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn check_output_file() -> Result<()> {
+    with_tmp_file("test.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        let output = Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env("CLICOLOR_FORCE", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args([
+                "check",
+                "--output-format",
+                "mdl",
+                "--output-file",
+                "json=mado.json",
+                "--output-file",
+                "concise=mado.txt",
+            ])
+            .output()
+            .into_diagnostic()?;
+        assert!(!output.status.success());
+        let stdout = String::from_utf8(output.stdout).into_diagnostic()?;
+        assert!(stdout.contains("\u{1b}[1;31mMD026\u{1b}[0m Trailing punctuation in header"));
+
+        let json = fs::read_to_string(dir.join("mado.json")).into_diagnostic()?;
+        let report: serde_json::Value = serde_json::from_str(&json).into_diagnostic()?;
+        assert_eq!(report["violations"][0]["rule"], "MD026");
+        // Files never contain colors
+        let text = fs::read_to_string(dir.join("mado.txt")).into_diagnostic()?;
+        assert_eq!(
+            text,
+            indoc! {"
+                ./test.md:1:1: MD026 Trailing punctuation in header
+
+                Found 1 error.
+            "}
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn check_output_formats() -> Result<()> {
+    with_tmp_file("test.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args([
+                "check",
+                "--output-format",
+                "concise",
+                "--output-format",
+                "github",
+                "--output-file",
+                "json=a.json",
+                "--output-file",
+                "json=b.json",
+            ])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+                ./test.md:1:1: MD026 Trailing punctuation in header
+
+                Found 1 error.
+                ::error file=test.md,line=1,col=1,endLine=1,endColumn=8,title=MD026::Trailing punctuation in header
+
+                Found 1 error.
+            "});
+
+        // Files of the same format are all written
+        let a = fs::read_to_string(dir.join("a.json")).into_diagnostic()?;
+        let b = fs::read_to_string(dir.join("b.json")).into_diagnostic()?;
+        assert_eq!(a, b);
+        Ok(())
+    })
+}
+
+#[test]
+fn check_reporters_config() -> Result<()> {
+    let config = indoc! {r#"
+        [lint.reporters]
+        checkstyle = "mado.xml"
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Hello\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert()
+            .success()
+            .stdout("All checks passed!\n");
+
+        let xml = fs::read_to_string(dir.join("mado.xml")).into_diagnostic()?;
+        assert_eq!(
+            xml,
+            indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <checkstyle version="4.3">
+                </checkstyle>
+            "#}
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn check_cache() -> Result<()> {
+    with_tmp_file("README.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--no-cache"])
+            .assert()
+            .failure();
+        assert!(!dir.join(".mado_cache").exists());
+
+        // Cached violations are reported in the same way
+        for _ in 0..2 {
+            Command::new(cargo_bin!("mado"))
+                .current_dir(dir)
+                .env_remove("CLICOLOR_FORCE")
+                .env("NO_COLOR", "1")
+                .env_remove("GITHUB_ACTIONS")
+                .args(["check", "--output-format", "mdl"])
+                .assert()
+                .failure()
+                .stdout(indoc! {"
+                    ./README.md:1: MD026 Trailing punctuation in header
+
+                    Found 1 error.
+                "});
+        }
+        assert!(dir.join(".mado_cache").is_dir());
+        Ok(())
+    })
+}
+
+#[test]
+fn check_cache_unavailable() -> Result<()> {
+    with_tmp_file("README.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        // The cache directory cannot be created where a file exists
+        fs::write(dir.join(".mado_cache"), "").into_diagnostic()?;
+
+        let output = Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--output-format", "mdl"])
+            .output()
+            .into_diagnostic()?;
+        assert!(!output.status.success());
+        let stdout = String::from_utf8(output.stdout).into_diagnostic()?;
+        assert_eq!(
+            stdout,
+            "./README.md:1: MD026 Trailing punctuation in header\n\nFound 1 error.\n"
+        );
+        let stderr = String::from_utf8(output.stderr).into_diagnostic()?;
+        assert!(
+            stderr.starts_with("warning: Failed to open the cache, so files are linted without it")
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn check_baseline() -> Result<()> {
+    with_tmp_file("README.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--write-baseline", "mado-baseline.json"])
+            .assert()
+            .success()
+            .stdout("Wrote 1 error to mado-baseline.json.\n");
+
+        // Only new violations are reported after the old one moves
+        fs::write(dir.join("README.md"), "# Title\n\n# Hello.\n\n# World!\n").into_diagnostic()?;
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args([
+                "check",
+                "--baseline",
+                "mado-baseline.json",
+                "--output-format",
+                "mdl",
+            ])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+                ./README.md:3: MD025 Multiple top level headers in the same document
+                ./README.md:5: MD025 Multiple top level headers in the same document
+                ./README.md:5: MD026 Trailing punctuation in header
+
+                Found 3 errors.
+            "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_baseline_not_found() -> Result<()> {
+    let output = Command::new(cargo_bin!("mado"))
+        .env_remove("GITHUB_ACTIONS")
+        .args(["check", "--baseline", "no-such-baseline.json", "README.md"])
+        .output()
+        .into_diagnostic()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).into_diagnostic()?;
+    assert!(stderr.contains("Failed to read no-such-baseline.json"));
+    Ok(())
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "user.name=mado", "-c", "user.email=mado@example.com"])
+        .args(args)
+        .assert()
+        .success();
+}
+
+#[test]
+fn check_changed_since() -> Result<()> {
+    with_tmp_file("README.md", "# Hello.\n\nText\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::write(dir.join("other.md"), "# Other.\n").into_diagnostic()?;
+        git(dir, &["init", "--quiet"]);
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "--quiet", "-m", "Initial commit"]);
+
+        fs::write(dir.join("README.md"), "# Hello.\n\nText\n\n# World!\n").into_diagnostic()?;
+        fs::write(dir.join("new.md"), "# New.\n").into_diagnostic()?;
+
+        // Only changed lines and untracked files are reported
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--changed-since", "HEAD", "--output-format", "mdl"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+                README.md:5: MD025 Multiple top level headers in the same document
+                README.md:5: MD026 Trailing punctuation in header
+                new.md:1: MD026 Trailing punctuation in header
+
+                Found 3 errors.
+            "});
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args([
+                "check",
+                "--changed-since",
+                "HEAD",
+                "--whole-file",
+                "--output-format",
+                "mdl",
+                "README.md",
+            ])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+                README.md:5: MD025 Multiple top level headers in the same document
+                README.md:1: MD026 Trailing punctuation in header
+                README.md:5: MD026 Trailing punctuation in header
+
+                Found 3 errors.
+            "});
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--changed-since", "HEAD", "other.md"])
+            .assert()
+            .success()
+            .stdout("All checks passed!\n");
+        Ok(())
+    })
+}
+
+#[test]
+fn check_changed_since_unknown_revision() -> Result<()> {
+    let tmp_dir = tempdir().into_diagnostic()?;
+    git(tmp_dir.path(), &["init", "--quiet"]);
+    let output = Command::new(cargo_bin!("mado"))
+        .current_dir(tmp_dir.path())
+        .env_remove("GITHUB_ACTIONS")
+        .args(["check", "--changed-since", "no-such-rev"])
+        .output()
+        .into_diagnostic()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).into_diagnostic()?;
+    assert!(stderr.contains("Failed to run git"));
+    assert!(stderr.contains("no-such-rev"));
+    Ok(())
+}
+
+#[test]
+fn check_severities() -> Result<()> {
+    let config = indoc! {r#"
+        [lint.severities]
+        MD026 = "warning"
+        headers = "info"
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Hello.\n\n### World\n\nText \n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./README.md:1:1: MD026 [warning] Trailing punctuation in header
+            ./README.md:3:1: MD001 [info] Header levels should only increment by one level at a time
+            ./README.md:5:5: MD009 Trailing spaces
+
+            Found 1 error, 1 warning and 1 info message.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_max_warnings() -> Result<()> {
+    let config = indoc! {r#"
+        [lint.severities]
+        MD026 = "warning"
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Hello.\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--max-warnings", "1"])
+            .assert()
+            .success();
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--max-warnings", "0"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./README.md:1:1: MD026 [warning] Trailing punctuation in header
+
+            Found 1 warning.
+        "})
+            .stderr("Found more warnings than the maximum of 0.\n");
+        Ok(())
+    })
+}
+
+#[test]
+fn check_output_file_invalid() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd.args(["check", "--output-file", "json"]).assert();
+    assert.failure().stderr(indoc! {"
+        error: invalid value 'json' for '--output-file <FORMAT=PATH>': expected FORMAT=PATH, but got `json`
+
+        For more information, try '--help'.
+    "});
+}
+
+#[test]
+fn check_watch_with_fix() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd.args(["check", "--watch", "--fix"]).assert();
+    assert.failure().stderr(indoc! {"
+        error: the argument '--watch' cannot be used with '--fix'
+
+        Usage: mado check --watch [FILES]...
+
+        For more information, try '--help'.
+    "});
+}
+
+#[test]
+fn check_stdin_full() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .env_remove("CLICOLOR_FORCE")
+        .env("NO_COLOR", "1")
+        .write_stdin("# Hello.\n")
+        .args(["check", "--output-format", "full"])
+        .assert();
+    assert.failure().stdout(indoc! {"
+        MD026/no-trailing-punctuation
+
+          × Trailing punctuation in header
+           ╭─[(stdin):1:1]
+         1 │ # Hello.
+           · ────────
+           ╰────
+          help: See https://github.com/markdownlint/markdownlint/blob/main/docs/RULES.md#md026
+
+
+        Found 1 error.
+    "});
+}
+
 #[test]
 fn check_empty_stdin() {
     let mut cmd = Command::new(cargo_bin!("mado"));
@@ -258,3 +881,321 @@ fn check_exclusion_default_target_with_dot_slash_prefix() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn check_fix() -> Result<()> {
+    with_tmp_file("test.md", "Text  \n\n\n\n#Hello\twith tab", |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .args(["check", "--fix", path_str])
+            .assert();
+        assert.failure().stdout(formatdoc! {"
+            {path_str}:1:1: MD041 First line in file should be a top level header
+
+            Found 1 error.
+        "});
+
+        let actual = fs::read_to_string(&path).into_diagnostic()?;
+        assert_eq!(actual, "Text\n\n# Hello  with tab\n");
+        Ok(())
+    })
+}
+
+#[test]
+fn check_diff() -> Result<()> {
+    let content = "#Hello  \n\nSee http://example.com.";
+    with_tmp_file("test.md", content, |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .args(["check", "--diff", path_str])
+            .assert();
+        assert
+            .failure()
+            .stdout(format!(
+                "--- {path_str}\n+++ {path_str}\n@@ -1,3 +1,3 @@\n-#Hello  \n+# Hello\n \n\
+                 -See http://example.com.\n\\ No newline at end of file\n\
+                 +See <http://example.com>.\n"
+            ))
+            .stderr("\nWould fix 4 errors.\n");
+
+        let actual = fs::read_to_string(&path).into_diagnostic()?;
+        assert_eq!(actual, content);
+        Ok(())
+    })
+}
+
+#[test]
+fn check_diff_stdin() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .env_remove("CLICOLOR_FORCE")
+        .env("NO_COLOR", "1")
+        .write_stdin("# Hello\n\nText\n")
+        .args(["check", "--diff"])
+        .assert();
+    assert.success().stdout("");
+}
+
+#[test]
+fn check_report_unused_suppressions() -> Result<()> {
+    let content = indoc! {"
+        # Hello
+
+        <!-- markdownlint-disable-next-line MD013 -->
+        Text
+        <!-- mado-disable-line no-such-rule -->
+    "};
+    with_tmp_file("test.md", content, |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .args(["check", "--report-unused-suppressions", path_str])
+            .assert();
+        assert.failure().stdout(formatdoc! {"
+            {path_str}:3:1: unused-suppression Suppression comment does not suppress any violations
+            {path_str}:5:1: unknown-suppression Suppression comment refers to an unknown rule
+
+            Found 2 errors.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_unused_suppression_severity() -> Result<()> {
+    let config = indoc! {r#"
+        [lint]
+        report-unused-suppressions = true
+
+        [lint.severities]
+        unused-suppression = "warning"
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        let content = "# Hello\n\n<!-- markdownlint-disable-next-line MD013 -->\nText\n";
+        fs::write(dir.join("README.md"), content).into_diagnostic()?;
+
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let assert = cmd
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert();
+        assert.success().stdout(indoc! {"
+            ./README.md:3:1: unused-suppression [warning] Suppression comment does not suppress any violations
+
+            Found 1 warning.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_overrides() -> Result<()> {
+    let config = indoc! {r#"
+        [[lint.overrides]]
+        files = ["CHANGELOG.md"]
+        disable-rules = ["line-length"]
+
+        [[lint.overrides]]
+        files = ["docs/**"]
+
+        [lint.overrides.md026]
+        punctuation = "!"
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        let content = format!("# Hello.\n\n{}\n", "Text ".repeat(20).trim_end());
+        fs::write(dir.join("CHANGELOG.md"), &content).into_diagnostic()?;
+        fs::write(dir.join("README.md"), &content).into_diagnostic()?;
+        fs::create_dir(dir.join("docs")).into_diagnostic()?;
+        fs::write(dir.join("docs").join("index.md"), "# Hello.\n").into_diagnostic()?;
+
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let assert = cmd
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert();
+        assert.failure().stdout(indoc! {"
+            ./CHANGELOG.md:1:1: MD026 Trailing punctuation in header
+            ./README.md:1:1: MD026 Trailing punctuation in header
+            ./README.md:3:81: MD013 Line length
+
+            Found 3 errors.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_nearest_config() -> Result<()> {
+    let config = indoc! {r#"
+        [lint]
+        rules = ["MD026"]
+    "#};
+    with_tmp_file("mado.toml", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        let package_dir = dir.join("packages").join("foo");
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::create_dir_all(package_dir.join("docs")).into_diagnostic()?;
+        let package_config = "[lint]\nrules = ['MD018']\n";
+        fs::write(package_dir.join(".mado.toml"), package_config).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Hello.\n").into_diagnostic()?;
+        fs::write(package_dir.join("README.md"), "#Hello.\n").into_diagnostic()?;
+        fs::write(package_dir.join("docs").join("index.md"), "#Hello.\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./README.md:1:1: MD026 Trailing punctuation in header
+            ./packages/foo/README.md:1:1: MD018 No space after hash on atx style header
+            ./packages/foo/docs/index.md:1:1: MD018 No space after hash on atx style header
+
+            Found 3 errors.
+        "});
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(package_dir.join("docs"))
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./index.md:1:1: MD018 No space after hash on atx style header
+
+            Found 1 error.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_nearest_config_exclusion() -> Result<()> {
+    with_tmp_file("README.md", "# Hello.\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        let package_dir = dir.join("packages").join("foo");
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::create_dir_all(package_dir.join("docs")).into_diagnostic()?;
+        let package_config = "[lint]\nexclude = ['docs/*.md']\n";
+        fs::write(package_dir.join("mado.toml"), package_config).into_diagnostic()?;
+        fs::write(package_dir.join("README.md"), "# Hello.\n").into_diagnostic()?;
+        fs::write(package_dir.join("docs").join("index.md"), "# Hello.\n").into_diagnostic()?;
+
+        // Exclusions of nested configurations are relative to them
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check", "--output-format", "mdl"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+                ./README.md:1: MD026 Trailing punctuation in header
+                ./packages/foo/README.md:1: MD026 Trailing punctuation in header
+
+                Found 2 errors.
+            "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_markdownlint_config() -> Result<()> {
+    let config = indoc! {r#"
+        {
+          "default": false,
+          "no-missing-space-atx": true
+        }
+    "#};
+    with_tmp_file(".markdownlint.json", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "#Hello.\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .assert()
+            .failure()
+            .stdout(indoc! {"
+            ./README.md:1:1: MD018 No space after hash on atx style header
+
+            Found 1 error.
+        "});
+        Ok(())
+    })
+}
+
+#[test]
+fn check_markdownlint_config_warnings() -> Result<()> {
+    let config = indoc! {r#"
+        {
+          "no-missing-space-atx": true,
+          "link-fragments": true
+        }
+    "#};
+    with_tmp_file(".markdownlint.json", config, |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        fs::write(dir.join("README.md"), "# Hello\n").into_diagnostic()?;
+
+        let output = Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .env_remove("CLICOLOR_FORCE")
+            .env("NO_COLOR", "1")
+            .env_remove("GITHUB_ACTIONS")
+            .args(["check"])
+            .output()
+            .into_diagnostic()?;
+        assert!(output.status.success());
+        let stderr = String::from_utf8(output.stderr).into_diagnostic()?;
+        let expected = format!(
+            "warning: rule `link-fragments` is not supported in {}\n",
+            path.display()
+        );
+        assert_eq!(stderr, expected);
+        Ok(())
+    })
+}
+
+#[test]
+fn check_markdownlint_config_with_config_option() -> Result<()> {
+    with_tmp_file(".markdownlint.yaml", "MD026: false\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::write(dir.join("README.md"), "# Hello.\n").into_diagnostic()?;
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .args(["--config", path_str, "check"])
+            .assert()
+            .success()
+            .stdout("All checks passed!\n");
+        Ok(())
+    })
+}
@@ -0,0 +1,108 @@
+use std::fs;
+
+use assert_cmd::Command;
+use assert_cmd::cargo_bin;
+use indoc::indoc;
+use miette::IntoDiagnostic as _;
+use miette::Result;
+use tempfile::tempdir;
+
+#[test]
+fn config_migrate() -> Result<()> {
+    let tmp_dir = tempdir().into_diagnostic()?;
+    let content = indoc! {r#"
+        {
+          // Disable long lines in tables
+          "line-length": { "line_length": 100, "tables": false },
+          "no-inline-html": false,
+        }
+    "#};
+    fs::write(tmp_dir.path().join(".markdownlint.jsonc"), content).into_diagnostic()?;
+
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .current_dir(tmp_dir.path())
+        .args(["config", "migrate"])
+        .assert();
+    assert.success().stdout(indoc! {r#"
+        [lint]
+        rules = [
+            "MD001",
+            "MD002",
+            "MD003",
+            "MD004",
+            "MD005",
+            "MD006",
+            "MD007",
+            "MD009",
+            "MD010",
+            "MD012",
+            "MD013",
+            "MD014",
+            "MD018",
+            "MD019",
+            "MD020",
+            "MD021",
+            "MD022",
+            "MD023",
+            "MD024",
+            "MD025",
+            "MD026",
+            "MD027",
+            "MD028",
+            "MD029",
+            "MD030",
+            "MD031",
+            "MD032",
+            "MD034",
+            "MD035",
+            "MD036",
+            "MD037",
+            "MD038",
+            "MD039",
+            "MD040",
+            "MD041",
+            "MD046",
+            "MD047",
+        ]
+
+        [lint.md013]
+        line-length = 100
+        tables = false
+    "#});
+    Ok(())
+}
+
+#[test]
+fn config_migrate_with_warnings() -> Result<()> {
+    let tmp_dir = tempdir().into_diagnostic()?;
+    let path = tmp_dir.path().join(".markdownlint.yaml");
+    fs::write(&path, "default: false\nMD001: true\nMD053: true\n").into_diagnostic()?;
+
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .env_remove("CLICOLOR_FORCE")
+        .env("NO_COLOR", "1")
+        .arg("config")
+        .arg("migrate")
+        .arg(&path)
+        .assert();
+    assert
+        .success()
+        .stdout("[lint]\nrules = [\"MD001\"]\n")
+        .stderr("warning: rule `MD053` is not supported\n");
+    Ok(())
+}
+
+#[test]
+fn config_migrate_not_found() -> Result<()> {
+    let tmp_dir = tempdir().into_diagnostic()?;
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .current_dir(tmp_dir.path())
+        .env("NO_COLOR", "1")
+        .args(["config", "migrate"])
+        .assert();
+    assert.failure();
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::fs::{self, File};
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use assert_cmd::cargo_bin;
+use indoc::formatdoc;
+use indoc::indoc;
+use miette::Context as _;
+use miette::IntoDiagnostic as _;
+use miette::Result;
+use tempfile::tempdir;
+
+fn with_tmp_file<F>(name: &str, content: &str, f: F) -> Result<()>
+where
+    F: FnOnce(PathBuf) -> Result<()>,
+{
+    let tmp_dir = tempdir().into_diagnostic()?;
+    let path = tmp_dir.path().join(name);
+    let mut tmp_file = File::create(path.clone()).into_diagnostic()?;
+    write!(tmp_file, "{content}").into_diagnostic()?;
+
+    f(path)?;
+
+    tmp_dir.close().into_diagnostic()
+}
+
+#[test]
+fn fmt_check() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd.write_stdin("").args(["fmt", "--check", "."]).assert();
+    assert.success();
+}
+
+#[test]
+fn fmt_stdin() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .write_stdin(indoc! {"
+            # Heading
+
+            *   Foo
+            +   Bar
+
+            Text
+
+                Code
+        "})
+        .args(["fmt"])
+        .assert();
+    assert.success().stdout(indoc! {"
+        # Heading
+
+        * Foo
+        * Bar
+
+        Text
+
+        ```
+        Code
+        ```
+    "});
+}
+
+#[test]
+fn fmt_stdin_check() {
+    let mut cmd = Command::new(cargo_bin!("mado"));
+    let assert = cmd
+        .write_stdin("# Heading\n\n+ Foo\n- Bar\n")
+        .args(["fmt", "--check"])
+        .assert();
+    assert.failure().stdout("Would reformat: (stdin)\n");
+}
+
+#[test]
+fn fmt_file() -> Result<()> {
+    with_tmp_file("test.md", "# Heading\n\n+ Foo\n- Bar\n", |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd.write_stdin("").args(["fmt", path_str]).assert();
+        assert
+            .success()
+            .stdout("1 file reformatted, 0 files left unchanged\n");
+
+        let actual = fs::read_to_string(&path).into_diagnostic()?;
+        assert_eq!(actual, "# Heading\n\n+ Foo\n+ Bar\n");
+        Ok(())
+    })
+}
+
+#[test]
+fn fmt_file_check() -> Result<()> {
+    let content = "# Heading\n\n+ Foo\n- Bar\n";
+    with_tmp_file("test.md", content, |path| {
+        let mut cmd = Command::new(cargo_bin!("mado"));
+        let path_str = path.to_str().wrap_err("failed to convert string")?;
+        let assert = cmd
+            .write_stdin("")
+            .args(["fmt", "--check", path_str])
+            .assert();
+        assert.failure().stdout(formatdoc! {"
+            Would reformat: {path_str}
+            1 file would be reformatted, 0 files already formatted
+        "});
+
+        let actual = fs::read_to_string(&path).into_diagnostic()?;
+        assert_eq!(actual, content);
+        Ok(())
+    })
+}
+
+#[test]
+fn fmt_with_discovered_config() -> Result<()> {
+    with_tmp_file("README.md", "+ Foo\n- Bar\n", |path| {
+        let dir = path.parent().wrap_err("failed to get parent dir")?;
+        fs::create_dir(dir.join(".git")).into_diagnostic()?;
+        let docs_dir = dir.join("docs");
+        fs::create_dir(&docs_dir).into_diagnostic()?;
+        let config = indoc! {r#"
+            [lint]
+            exclude = ["drafts/*.md"]
+
+            [lint.md004]
+            style = "asterisk"
+        "#};
+        fs::write(docs_dir.join("mado.toml"), config).into_diagnostic()?;
+        fs::write(docs_dir.join("index.md"), "+ Foo\n- Bar\n").into_diagnostic()?;
+        fs::create_dir(docs_dir.join("drafts")).into_diagnostic()?;
+        let draft_path = docs_dir.join("drafts").join("draft.md");
+        fs::write(&draft_path, "+ Foo\n- Bar\n").into_diagnostic()?;
+
+        Command::new(cargo_bin!("mado"))
+            .current_dir(dir)
+            .write_stdin("")
+            .args(["fmt"])
+            .assert()
+            .success()
+            .stdout("2 files reformatted, 0 files left unchanged\n");
+
+        let actual = fs::read_to_string(&path).into_diagnostic()?;
+        assert_eq!(actual, "+ Foo\n+ Bar\n");
+        let docs_actual = fs::read_to_string(docs_dir.join("index.md")).into_diagnostic()?;
+        assert_eq!(docs_actual, "* Foo\n* Bar\n");
+        let draft_actual = fs::read_to_string(&draft_path).into_diagnostic()?;
+        assert_eq!(draft_actual, "+ Foo\n- Bar\n");
+        Ok(())
+    })
+}